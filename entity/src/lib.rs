@@ -2,9 +2,18 @@
 
 pub mod prelude;
 
+pub mod booking;
+pub mod genre;
 pub mod movie;
+pub mod movie_genre;
+pub mod payment_event;
+pub mod refresh_token;
 pub mod room;
+pub mod sea_orm_active_enums;
+pub mod seat_hold;
 pub mod showtime;
 pub mod showtime_room;
 pub mod taken_seat;
 pub mod theater;
+pub mod ticket;
+pub mod user;
@@ -11,21 +11,45 @@ pub struct Model {
     pub overview: String,
     #[sea_orm(column_type = "Float")]
     pub rating: f32,
-    pub genre: String,
     #[sea_orm(column_type = "Text")]
     pub poster_url: String,
+    pub runtime_minutes: Option<i32>,
+    pub release_date: Option<Date>,
+    pub age_rating: Option<String>,
+    pub language: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub trailer_url: Option<String>,
+    pub director: Option<String>,
+    pub cast_members: Vec<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::movie_genre::Entity")]
+    MovieGenre,
     #[sea_orm(has_many = "super::showtime::Entity")]
     Showtime,
 }
 
+impl Related<super::movie_genre::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::MovieGenre.def()
+    }
+}
+
 impl Related<super::showtime::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Showtime.def()
     }
 }
 
+impl Related<super::genre::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::movie_genre::Relation::Genre.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::movie_genre::Relation::Movie.def().rev())
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
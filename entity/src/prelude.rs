@@ -1,8 +1,16 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 2.0.0-rc.9
 
+pub use super::booking::Entity as Booking;
+pub use super::genre::Entity as Genre;
 pub use super::movie::Entity as Movie;
+pub use super::movie_genre::Entity as MovieGenre;
+pub use super::payment_event::Entity as PaymentEvent;
+pub use super::refresh_token::Entity as RefreshToken;
 pub use super::room::Entity as Room;
+pub use super::seat_hold::Entity as SeatHold;
 pub use super::showtime::Entity as Showtime;
 pub use super::showtime_room::Entity as ShowtimeRoom;
 pub use super::taken_seat::Entity as TakenSeat;
 pub use super::theater::Entity as Theater;
+pub use super::ticket::Entity as Ticket;
+pub use super::user::Entity as User;
@@ -14,6 +14,8 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::booking::Entity")]
+    Booking,
     #[sea_orm(
         belongs_to = "super::movie::Entity",
         from = "Column::MovieId",
@@ -22,18 +24,32 @@ pub enum Relation {
         on_delete = "NoAction"
     )]
     Movie,
+    #[sea_orm(has_many = "super::seat_hold::Entity")]
+    SeatHold,
     #[sea_orm(has_many = "super::showtime_room::Entity")]
     ShowtimeRoom,
     #[sea_orm(has_many = "super::taken_seat::Entity")]
     TakenSeat,
 }
 
+impl Related<super::booking::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Booking.def()
+    }
+}
+
 impl Related<super::movie::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Movie.def()
     }
 }
 
+impl Related<super::seat_hold::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SeatHold.def()
+    }
+}
+
 impl Related<super::showtime_room::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::ShowtimeRoom.def()
@@ -7,7 +7,7 @@ use sea_orm::entity::prelude::*;
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
-    pub time: DateTime,
+    pub time: DateTimeWithTimeZone,
     pub price: i32,
     pub room_id: Uuid,
     pub showtime_id: Uuid,
@@ -15,6 +15,8 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::booking::Entity")]
+    Booking,
     #[sea_orm(
         belongs_to = "super::room::Entity",
         from = "Column::RoomId",
@@ -23,6 +25,8 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Room,
+    #[sea_orm(has_many = "super::seat_hold::Entity")]
+    SeatHold,
     #[sea_orm(
         belongs_to = "super::showtime::Entity",
         from = "Column::ShowtimeId",
@@ -35,12 +39,24 @@ pub enum Relation {
     TakenSeat,
 }
 
+impl Related<super::booking::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Booking.def()
+    }
+}
+
 impl Related<super::room::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Room.def()
     }
 }
 
+impl Related<super::seat_hold::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SeatHold.def()
+    }
+}
+
 impl Related<super::showtime::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Showtime.def()
@@ -10,10 +10,19 @@ pub struct Model {
     pub showtime_id: Uuid,
     pub showtime_room_id: i32,
     pub seat_identifier: String,
+    pub booking_id: Option<Uuid>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::booking::Entity",
+        from = "Column::BookingId",
+        to = "super::booking::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Booking,
     #[sea_orm(
         belongs_to = "super::showtime::Entity",
         from = "Column::ShowtimeId",
@@ -30,6 +39,14 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     ShowtimeRoom,
+    #[sea_orm(has_one = "super::ticket::Entity")]
+    Ticket,
+}
+
+impl Related<super::booking::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Booking.def()
+    }
 }
 
 impl Related<super::showtime::Entity> for Entity {
@@ -44,4 +61,10 @@ impl Related<super::showtime_room::Entity> for Entity {
     }
 }
 
+impl Related<super::ticket::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Ticket.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
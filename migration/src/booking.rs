@@ -0,0 +1,66 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveIden)]
+pub enum Booking {
+    Table,
+    Id,
+    ShowtimeId,
+    ShowtimeRoomId,
+    UserId,
+    Seats,
+    Amount,
+    Status,
+    RefundStatus,
+    CancellationReason,
+    CancelledAt,
+    CreatedAt,
+    UpdatedAt,
+    PaymentProvider,
+    PaymentIntentId,
+}
+
+#[derive(DeriveIden)]
+pub enum BookingStatus {
+    #[sea_orm(iden = "booking_status")]
+    Enum,
+    Confirmed,
+    Cancelled,
+    PendingPayment,
+    PaymentFailed,
+}
+
+#[derive(DeriveIden)]
+pub enum RefundStatus {
+    #[sea_orm(iden = "refund_status")]
+    Enum,
+    None,
+    Pending,
+    Refunded,
+}
+
+#[derive(DeriveIden)]
+pub enum PaymentEvent {
+    Table,
+    Id,
+    Provider,
+    EventType,
+    PaymentIntentId,
+    BookingId,
+    OccurredAt,
+    ReceivedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum Ticket {
+    Table,
+    Id,
+    TakenSeatId,
+    BookingId,
+    CreatedAt,
+    CheckedInAt,
+    CheckedInBy,
+    ShowtimeId,
+    ShowtimeRoomId,
+    SeatIdentifier,
+    VoidedAt,
+}
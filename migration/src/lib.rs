@@ -1,14 +1,54 @@
 pub use sea_orm_migration::prelude::*;
 
+mod booking;
 mod m20220101_000001_create_table;
+mod m20261018_000001_create_seat_hold_table;
+mod m20261018_000002_add_room_layout;
+mod m20261018_000003_create_booking_table;
+mod m20261018_000004_create_user_table;
+mod m20261018_000005_create_refresh_token_table;
+mod m20261018_000006_add_user_role;
+mod m20261018_000007_add_movie_runtime;
+mod m20261018_000008_add_movie_metadata;
+mod m20261018_000009_create_genre_table;
+mod m20261018_000010_add_movie_search;
+mod m20261018_000011_add_theater_timezone;
+mod m20261018_000012_add_theater_geolocation;
+mod m20261018_000013_add_booking_payment;
+mod m20261018_000014_create_payment_event_table;
+mod m20261018_000015_create_ticket_table;
+mod m20261018_000016_add_ticket_check_in;
+mod m20261018_000017_add_seat_hold_user;
+mod m20261018_000018_add_ticket_voided_at;
 mod movie;
 mod theater;
+mod user;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_table::Migration)]
+        vec![
+            Box::new(m20220101_000001_create_table::Migration),
+            Box::new(m20261018_000001_create_seat_hold_table::Migration),
+            Box::new(m20261018_000002_add_room_layout::Migration),
+            Box::new(m20261018_000003_create_booking_table::Migration),
+            Box::new(m20261018_000004_create_user_table::Migration),
+            Box::new(m20261018_000005_create_refresh_token_table::Migration),
+            Box::new(m20261018_000006_add_user_role::Migration),
+            Box::new(m20261018_000007_add_movie_runtime::Migration),
+            Box::new(m20261018_000008_add_movie_metadata::Migration),
+            Box::new(m20261018_000009_create_genre_table::Migration),
+            Box::new(m20261018_000010_add_movie_search::Migration),
+            Box::new(m20261018_000011_add_theater_timezone::Migration),
+            Box::new(m20261018_000012_add_theater_geolocation::Migration),
+            Box::new(m20261018_000013_add_booking_payment::Migration),
+            Box::new(m20261018_000014_create_payment_event_table::Migration),
+            Box::new(m20261018_000015_create_ticket_table::Migration),
+            Box::new(m20261018_000016_add_ticket_check_in::Migration),
+            Box::new(m20261018_000017_add_seat_hold_user::Migration),
+            Box::new(m20261018_000018_add_ticket_voided_at::Migration),
+        ]
     }
 }
@@ -0,0 +1,80 @@
+use crate::theater::{SeatHold, Showtime, ShowtimeRoom};
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create seat_hold table
+        let _ = manager
+            .create_table(
+                Table::create()
+                    .table(SeatHold::Table)
+                    .if_not_exists()
+                    .col(pk_auto(SeatHold::Id).not_null())
+                    .col(uuid(SeatHold::HoldId).not_null())
+                    .col(uuid(SeatHold::ShowtimeId).not_null())
+                    .col(integer(SeatHold::ShowtimeRoomId).not_null())
+                    .col(string_len(SeatHold::SeatIdentifier, 3).not_null())
+                    .col(date_time(SeatHold::ExpiresAt).not_null())
+                    .col(
+                        date_time(SeatHold::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .name("fk_seat_hold_showtime")
+                            .from_tbl(SeatHold::Table)
+                            .from_col(SeatHold::ShowtimeId)
+                            .to_tbl(Showtime::Table)
+                            .to_col(Showtime::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .name("fk_seat_hold_showtime_room")
+                            .from_tbl(SeatHold::Table)
+                            .from_col(SeatHold::ShowtimeRoomId)
+                            .to_tbl(ShowtimeRoom::Table)
+                            .to_col(ShowtimeRoom::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // A seat can only be held once at a time
+        let _ = manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("uq_seat_hold_showtime_id_seat_identifier")
+                    .table(SeatHold::Table)
+                    .col(SeatHold::ShowtimeId)
+                    .col(SeatHold::ShowtimeRoomId)
+                    .col(SeatHold::SeatIdentifier)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Used by the reaper to find expired holds
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("idx_seat_hold_expires_at")
+                    .table(SeatHold::Table)
+                    .col(SeatHold::ExpiresAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SeatHold::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,31 @@
+use crate::theater::Room;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Rooms without a layout keep using the full max_rows * max_columns grid
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Room::Table)
+                    .add_column_if_not_exists(json_binary_null(Room::Layout))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Room::Table)
+                    .drop_column(Room::Layout)
+                    .to_owned(),
+            )
+            .await
+    }
+}
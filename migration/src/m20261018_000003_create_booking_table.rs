@@ -0,0 +1,132 @@
+use crate::booking::{Booking, BookingStatus, RefundStatus};
+use crate::theater::{Showtime, ShowtimeRoom, TakenSeat};
+use sea_orm_migration::{prelude::extension::postgres::Type, prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create booking_status and refund_status types
+        let _ = manager
+            .create_type(
+                Type::create()
+                    .as_enum(BookingStatus::Enum)
+                    .values([BookingStatus::Confirmed, BookingStatus::Cancelled])
+                    .to_owned(),
+            )
+            .await?;
+
+        let _ = manager
+            .create_type(
+                Type::create()
+                    .as_enum(RefundStatus::Enum)
+                    .values([
+                        RefundStatus::None,
+                        RefundStatus::Pending,
+                        RefundStatus::Refunded,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create bookings table
+        let _ = manager
+            .create_table(
+                Table::create()
+                    .table(Booking::Table)
+                    .if_not_exists()
+                    .col(pk_uuid(Booking::Id).not_null())
+                    .col(uuid(Booking::ShowtimeId).not_null())
+                    .col(integer(Booking::ShowtimeRoomId).not_null())
+                    .col(array(Booking::Seats, ColumnType::String(StringLen::N(3))).not_null())
+                    .col(integer(Booking::Amount).not_null())
+                    .col(custom(Booking::Status, BookingStatus::Enum).not_null())
+                    .col(
+                        custom(Booking::RefundStatus, RefundStatus::Enum)
+                            .not_null()
+                            .default(Expr::cust("'none'")),
+                    )
+                    .col(text_null(Booking::CancellationReason))
+                    .col(date_time_null(Booking::CancelledAt))
+                    .col(
+                        date_time(Booking::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        date_time(Booking::UpdatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .name("fk_booking_showtime")
+                            .from_tbl(Booking::Table)
+                            .from_col(Booking::ShowtimeId)
+                            .to_tbl(Showtime::Table)
+                            .to_col(Showtime::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .name("fk_booking_showtime_room")
+                            .from_tbl(Booking::Table)
+                            .from_col(Booking::ShowtimeRoomId)
+                            .to_tbl(ShowtimeRoom::Table)
+                            .to_col(ShowtimeRoom::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Seats taken before bookings existed are not part of any booking
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TakenSeat::Table)
+                    .add_column_if_not_exists(uuid_null(TakenSeat::BookingId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_taken_seat_booking")
+                            .from_tbl(TakenSeat::Table)
+                            .from_col(TakenSeat::BookingId)
+                            .to_tbl(Booking::Table)
+                            .to_col(Booking::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let _ = manager
+            .alter_table(
+                Table::alter()
+                    .table(TakenSeat::Table)
+                    .drop_foreign_key("fk_taken_seat_booking")
+                    .drop_column(TakenSeat::BookingId)
+                    .to_owned(),
+            )
+            .await?;
+
+        let _ = manager
+            .drop_table(Table::drop().table(Booking::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .if_exists()
+                    .names([
+                        SeaRc::new(BookingStatus::Enum) as DynIden,
+                        SeaRc::new(RefundStatus::Enum) as DynIden,
+                    ])
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,103 @@
+use crate::booking::Booking;
+use crate::user::{User, UserSession};
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create users table
+        let _ = manager
+            .create_table(
+                Table::create()
+                    .table(User::Table)
+                    .if_not_exists()
+                    .col(pk_uuid(User::Id).not_null())
+                    .col(string_uniq(User::Email).not_null())
+                    .col(string(User::Name).not_null())
+                    .col(text(User::PasswordHash).not_null())
+                    .col(
+                        date_time(User::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        date_time(User::UpdatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create user_sessions table
+        let _ = manager
+            .create_table(
+                Table::create()
+                    .table(UserSession::Table)
+                    .if_not_exists()
+                    .col(pk_uuid(UserSession::Id).not_null())
+                    .col(uuid(UserSession::UserId).not_null())
+                    .col(string_uniq(UserSession::TokenHash).not_null())
+                    .col(date_time(UserSession::ExpiresAt).not_null())
+                    .col(
+                        date_time(UserSession::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .name("fk_user_session_user")
+                            .from_tbl(UserSession::Table)
+                            .from_col(UserSession::UserId)
+                            .to_tbl(User::Table)
+                            .to_col(User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Bookings made before accounts existed do not belong to anyone
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Booking::Table)
+                    .add_column_if_not_exists(uuid_null(Booking::UserId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_booking_user")
+                            .from_tbl(Booking::Table)
+                            .from_col(Booking::UserId)
+                            .to_tbl(User::Table)
+                            .to_col(User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let _ = manager
+            .alter_table(
+                Table::alter()
+                    .table(Booking::Table)
+                    .drop_foreign_key("fk_booking_user")
+                    .drop_column(Booking::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(UserSession::Table)
+                    .table(User::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
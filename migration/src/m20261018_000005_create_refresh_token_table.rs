@@ -0,0 +1,95 @@
+use crate::user::{RefreshToken, User, UserSession};
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Sessions are replaced by stateless access tokens and refresh tokens
+        let _ = manager
+            .drop_table(
+                Table::drop()
+                    .table(UserSession::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create refresh_tokens table
+        let _ = manager
+            .create_table(
+                Table::create()
+                    .table(RefreshToken::Table)
+                    .if_not_exists()
+                    .col(pk_uuid(RefreshToken::Id).not_null())
+                    .col(uuid(RefreshToken::UserId).not_null())
+                    .col(uuid(RefreshToken::FamilyId).not_null())
+                    .col(string_uniq(RefreshToken::TokenHash).not_null())
+                    .col(date_time(RefreshToken::ExpiresAt).not_null())
+                    .col(date_time_null(RefreshToken::RevokedAt))
+                    .col(uuid_null(RefreshToken::ReplacedBy))
+                    .col(
+                        date_time(RefreshToken::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .name("fk_refresh_token_user")
+                            .from_tbl(RefreshToken::Table)
+                            .from_col(RefreshToken::UserId)
+                            .to_tbl(User::Table)
+                            .to_col(User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Used to revoke every token of a family at once
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("idx_refresh_token_family_id")
+                    .table(RefreshToken::Table)
+                    .col(RefreshToken::FamilyId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let _ = manager
+            .drop_table(Table::drop().table(RefreshToken::Table).to_owned())
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserSession::Table)
+                    .if_not_exists()
+                    .col(pk_uuid(UserSession::Id).not_null())
+                    .col(uuid(UserSession::UserId).not_null())
+                    .col(string_uniq(UserSession::TokenHash).not_null())
+                    .col(date_time(UserSession::ExpiresAt).not_null())
+                    .col(
+                        date_time(UserSession::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .name("fk_user_session_user")
+                            .from_tbl(UserSession::Table)
+                            .from_col(UserSession::UserId)
+                            .to_tbl(User::Table)
+                            .to_col(User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+}
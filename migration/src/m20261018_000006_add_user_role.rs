@@ -0,0 +1,67 @@
+use crate::theater::Theater;
+use crate::user::{User, UserRole};
+use sea_orm_migration::{prelude::extension::postgres::Type, prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create user_role type
+        let _ = manager
+            .create_type(
+                Type::create()
+                    .as_enum(UserRole::Enum)
+                    .values([
+                        UserRole::Customer,
+                        UserRole::BoxOffice,
+                        UserRole::TheaterManager,
+                        UserRole::Admin,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Existing accounts become customers; staff are scoped to the theater they work at
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column_if_not_exists(
+                        custom(User::Role, UserRole::Enum)
+                            .not_null()
+                            .default(Expr::cust("'customer'")),
+                    )
+                    .add_column_if_not_exists(uuid_null(User::TheaterId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_user_theater")
+                            .from_tbl(User::Table)
+                            .from_col(User::TheaterId)
+                            .to_tbl(Theater::Table)
+                            .to_col(Theater::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let _ = manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_foreign_key("fk_user_theater")
+                    .drop_column(User::Role)
+                    .drop_column(User::TheaterId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().if_exists().name(UserRole::Enum).to_owned())
+            .await
+    }
+}
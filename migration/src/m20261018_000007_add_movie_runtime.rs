@@ -0,0 +1,31 @@
+use crate::movie::Movie;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Movies without a runtime cannot be scheduled until it is filled in
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Movie::Table)
+                    .add_column_if_not_exists(integer_null(Movie::RuntimeMinutes))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Movie::Table)
+                    .drop_column(Movie::RuntimeMinutes)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,44 @@
+use crate::movie::Movie;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Movie::Table)
+                    .add_column_if_not_exists(date_null(Movie::ReleaseDate))
+                    .add_column_if_not_exists(string_len_null(Movie::AgeRating, 10))
+                    .add_column_if_not_exists(string_null(Movie::Language))
+                    .add_column_if_not_exists(text_null(Movie::TrailerUrl))
+                    .add_column_if_not_exists(string_null(Movie::Director))
+                    .add_column_if_not_exists(
+                        array(Movie::CastMembers, ColumnType::String(StringLen::None))
+                            .not_null()
+                            .default(Expr::cust("'{}'")),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Movie::Table)
+                    .drop_column(Movie::ReleaseDate)
+                    .drop_column(Movie::AgeRating)
+                    .drop_column(Movie::Language)
+                    .drop_column(Movie::TrailerUrl)
+                    .drop_column(Movie::Director)
+                    .drop_column(Movie::CastMembers)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,169 @@
+use crate::movie::{Genre, Movie, MovieGenre};
+use sea_orm_migration::{prelude::*, schema::*};
+
+const GENRES: [&str; 18] = [
+    "Action",
+    "Adventure",
+    "Animation",
+    "Comedy",
+    "Crime",
+    "Documentary",
+    "Drama",
+    "Family",
+    "Fantasy",
+    "History",
+    "Horror",
+    "Music",
+    "Mystery",
+    "Romance",
+    "Sci-Fi",
+    "Thriller",
+    "War",
+    "Western",
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create genres table
+        let _ = manager
+            .create_table(
+                Table::create()
+                    .table(Genre::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Genre::Id).not_null())
+                    .col(string_uniq(Genre::Name).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create movie_genres table
+        let _ = manager
+            .create_table(
+                Table::create()
+                    .table(MovieGenre::Table)
+                    .if_not_exists()
+                    .col(uuid(MovieGenre::MovieId).not_null())
+                    .col(integer(MovieGenre::GenreId).not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(MovieGenre::MovieId)
+                            .col(MovieGenre::GenreId),
+                    )
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .name("fk_movie_genre_movie")
+                            .from_tbl(MovieGenre::Table)
+                            .from_col(MovieGenre::MovieId)
+                            .to_tbl(Movie::Table)
+                            .to_col(Movie::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .name("fk_movie_genre_genre")
+                            .from_tbl(MovieGenre::Table)
+                            .from_col(MovieGenre::GenreId)
+                            .to_tbl(Genre::Table)
+                            .to_col(Genre::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Index for finding the movies of a genre
+        let _ = manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("idx_movie_genre_genre_id")
+                    .table(MovieGenre::Table)
+                    .col(MovieGenre::GenreId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Seed the genres movies can be filed under
+        let mut insert_genres = Query::insert();
+        insert_genres
+            .into_table(Genre::Table)
+            .columns([Genre::Name])
+            .on_conflict(OnConflict::column(Genre::Name).do_nothing().to_owned());
+        for genre in GENRES {
+            insert_genres.values_panic([genre.into()]);
+        }
+        let _ = manager.exec_stmt(insert_genres).await?;
+
+        // Split the existing comma separated genres, keeping any genre that is not seeded yet
+        let db = manager.get_connection();
+        let _ = db
+            .execute_unprepared(
+                r#"
+                INSERT INTO genre (name)
+                SELECT DISTINCT ON (lower(trim(g.name))) trim(g.name)
+                FROM movie m, unnest(string_to_array(m.genre, ',')) AS g(name)
+                WHERE trim(g.name) <> ''
+                  AND NOT EXISTS (SELECT 1 FROM genre WHERE lower(genre.name) = lower(trim(g.name)))
+                "#,
+            )
+            .await?;
+        let _ = db
+            .execute_unprepared(
+                r#"
+                INSERT INTO movie_genre (movie_id, genre_id)
+                SELECT DISTINCT m.id, genre.id
+                FROM movie m, unnest(string_to_array(m.genre, ',')) AS g(name)
+                JOIN genre ON lower(genre.name) = lower(trim(g.name))
+                "#,
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Movie::Table)
+                    .drop_column(Movie::Genre)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let _ = manager
+            .alter_table(
+                Table::alter()
+                    .table(Movie::Table)
+                    .add_column_if_not_exists(string(Movie::Genre).not_null().default(""))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Join the genres back into the comma separated column
+        let _ = manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                UPDATE movie m
+                SET genre = g.names
+                FROM (SELECT mg.movie_id, string_agg(genre.name, ', ' ORDER BY genre.name) AS names
+                      FROM movie_genre mg
+                               JOIN genre ON genre.id = mg.genre_id
+                      GROUP BY mg.movie_id) g
+                WHERE g.movie_id = m.id
+                "#,
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(MovieGenre::Table)
+                    .table(Genre::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,66 @@
+use crate::movie::Movie;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // Trigram matching for typo tolerant title search
+        let _ = db
+            .execute_unprepared("CREATE EXTENSION IF NOT EXISTS pg_trgm")
+            .await?;
+
+        // Title matches rank above overview matches
+        let _ = db
+            .execute_unprepared(
+                r#"
+                ALTER TABLE movie
+                    ADD COLUMN IF NOT EXISTS search_vector tsvector
+                        GENERATED ALWAYS AS (
+                            setweight(to_tsvector('english', coalesce(title, '')), 'A') ||
+                            setweight(to_tsvector('english', coalesce(overview, '')), 'B')
+                        ) STORED
+                "#,
+            )
+            .await?;
+
+        let _ = manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .if_not_exists()
+                    .name("idx_movie_search_vector")
+                    .table(Movie::Table)
+                    .col(Movie::SearchVector)
+                    .index_type(IndexType::Custom(SeaRc::new(Alias::new("GIN"))))
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS idx_movie_title_trgm ON movie USING GIN (title gin_trgm_ops)",
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let _ = manager
+            .get_connection()
+            .execute_unprepared("DROP INDEX IF EXISTS idx_movie_title_trgm")
+            .await?;
+
+        // Dropping the column also drops its index
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Movie::Table)
+                    .drop_column(Movie::SearchVector)
+                    .to_owned(),
+            )
+            .await
+    }
+}
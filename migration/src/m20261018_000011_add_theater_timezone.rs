@@ -0,0 +1,53 @@
+use crate::theater::Theater;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // IANA timezone the theater's showtimes are scheduled in
+        let _ = manager
+            .alter_table(
+                Table::alter()
+                    .table(Theater::Table)
+                    .add_column_if_not_exists(string(Theater::Timezone).not_null().default("UTC"))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Slot times have always been written in UTC
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE showtime_room
+                    ALTER COLUMN time TYPE timestamptz USING time AT TIME ZONE 'UTC'
+                "#,
+            )
+            .await
+            .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let _ = manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE showtime_room
+                    ALTER COLUMN time TYPE timestamp USING time AT TIME ZONE 'UTC'
+                "#,
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Theater::Table)
+                    .drop_column(Theater::Timezone)
+                    .to_owned(),
+            )
+            .await
+    }
+}
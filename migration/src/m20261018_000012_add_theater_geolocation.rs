@@ -0,0 +1,59 @@
+use crate::theater::Theater;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let _ = manager
+            .alter_table(
+                Table::alter()
+                    .table(Theater::Table)
+                    .add_column_if_not_exists(double_null(Theater::Latitude))
+                    .add_column_if_not_exists(double_null(Theater::Longitude))
+                    .add_column_if_not_exists(string_null(Theater::Address))
+                    .add_column_if_not_exists(string_null(Theater::City))
+                    .to_owned(),
+            )
+            .await?;
+
+        // A theater is either placed on the map or not at all
+        let _ = manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE theater
+                    ADD CONSTRAINT chk_theater_coordinates CHECK (
+                        (latitude IS NULL) = (longitude IS NULL)
+                        AND latitude BETWEEN -90 AND 90
+                        AND longitude BETWEEN -180 AND 180
+                    )
+                "#,
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_theater_city ON theater (lower(city))",
+            )
+            .await
+            .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Theater::Table)
+                    .drop_column(Theater::Latitude)
+                    .drop_column(Theater::Longitude)
+                    .drop_column(Theater::Address)
+                    .drop_column(Theater::City)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,84 @@
+use crate::booking::{Booking, BookingStatus};
+use sea_orm_migration::{prelude::extension::postgres::Type, prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Bookings wait for their payment to be captured before the seats are sold
+        let _ = manager
+            .alter_type(
+                Type::alter()
+                    .name(BookingStatus::Enum)
+                    .add_value(BookingStatus::PendingPayment)
+                    .if_not_exists(),
+            )
+            .await?;
+
+        let _ = manager
+            .alter_type(
+                Type::alter()
+                    .name(BookingStatus::Enum)
+                    .add_value(BookingStatus::PaymentFailed)
+                    .if_not_exists(),
+            )
+            .await?;
+
+        let _ = manager
+            .alter_table(
+                Table::alter()
+                    .table(Booking::Table)
+                    .add_column_if_not_exists(string_null(Booking::PaymentProvider))
+                    .add_column_if_not_exists(string_null(Booking::PaymentIntentId))
+                    .to_owned(),
+            )
+            .await?;
+
+        // A payment intent belongs to exactly one booking
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("uq_booking_payment_intent")
+                    .table(Booking::Table)
+                    .col(Booking::PaymentProvider)
+                    .col(Booking::PaymentIntentId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let _ = manager
+            .alter_table(
+                Table::alter()
+                    .table(Booking::Table)
+                    .drop_column(Booking::PaymentProvider)
+                    .drop_column(Booking::PaymentIntentId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Enum values cannot be dropped, so the type is recreated without them. Bookings that
+        // never got paid for are cancelled.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                UPDATE booking
+                SET status = 'cancelled', cancelled_at = coalesce(cancelled_at, updated_at)
+                WHERE status IN ('pending_payment', 'payment_failed');
+                ALTER TYPE booking_status RENAME TO booking_status_old;
+                CREATE TYPE booking_status AS ENUM ('confirmed', 'cancelled');
+                ALTER TABLE booking
+                    ALTER COLUMN status TYPE booking_status USING status::text::booking_status;
+                DROP TYPE booking_status_old;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
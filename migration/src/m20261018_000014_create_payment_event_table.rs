@@ -0,0 +1,62 @@
+use crate::booking::{Booking, PaymentEvent};
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create payment_event table, keyed by the provider's event id to ignore replays
+        let _ = manager
+            .create_table(
+                Table::create()
+                    .table(PaymentEvent::Table)
+                    .if_not_exists()
+                    .col(string(PaymentEvent::Id).not_null())
+                    .col(string(PaymentEvent::Provider).not_null())
+                    .col(string(PaymentEvent::EventType).not_null())
+                    .col(string(PaymentEvent::PaymentIntentId).not_null())
+                    .col(uuid_null(PaymentEvent::BookingId))
+                    .col(date_time(PaymentEvent::OccurredAt).not_null())
+                    .col(
+                        date_time(PaymentEvent::ReceivedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(PaymentEvent::Provider)
+                            .col(PaymentEvent::Id),
+                    )
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .name("fk_payment_event_booking")
+                            .from_tbl(PaymentEvent::Table)
+                            .from_col(PaymentEvent::BookingId)
+                            .to_tbl(Booking::Table)
+                            .to_col(Booking::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Index for finding the events of a booking
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("idx_payment_event_booking_id")
+                    .table(PaymentEvent::Table)
+                    .col(PaymentEvent::BookingId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PaymentEvent::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,80 @@
+use crate::booking::{Booking, Ticket};
+use crate::theater::TakenSeat;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create tickets table, one ticket per sold seat
+        let _ = manager
+            .create_table(
+                Table::create()
+                    .table(Ticket::Table)
+                    .if_not_exists()
+                    .col(pk_uuid(Ticket::Id).not_null())
+                    .col(integer_uniq(Ticket::TakenSeatId).not_null())
+                    .col(uuid(Ticket::BookingId).not_null())
+                    .col(
+                        date_time(Ticket::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .name("fk_ticket_taken_seat")
+                            .from_tbl(Ticket::Table)
+                            .from_col(Ticket::TakenSeatId)
+                            .to_tbl(TakenSeat::Table)
+                            .to_col(TakenSeat::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .name("fk_ticket_booking")
+                            .from_tbl(Ticket::Table)
+                            .from_col(Ticket::BookingId)
+                            .to_tbl(Booking::Table)
+                            .to_col(Booking::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Index for listing the tickets of a booking
+        let _ = manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("idx_ticket_booking_id")
+                    .table(Ticket::Table)
+                    .col(Ticket::BookingId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Issue tickets for the seats of bookings that are already confirmed
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                INSERT INTO ticket (id, taken_seat_id, booking_id)
+                SELECT gen_random_uuid(), ts.id, ts.booking_id
+                FROM taken_seat ts
+                         JOIN booking b ON b.id = ts.booking_id
+                WHERE b.status = 'confirmed'
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Ticket::Table).to_owned())
+            .await
+    }
+}
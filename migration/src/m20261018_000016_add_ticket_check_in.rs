@@ -0,0 +1,44 @@
+use crate::booking::Ticket;
+use crate::user::User;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // A ticket is used once it has been scanned at the door
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .add_column_if_not_exists(timestamp_with_time_zone_null(Ticket::CheckedInAt))
+                    .add_column_if_not_exists(uuid_null(Ticket::CheckedInBy))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_ticket_checked_in_by")
+                            .from_tbl(Ticket::Table)
+                            .from_col(Ticket::CheckedInBy)
+                            .to_tbl(User::Table)
+                            .to_col(User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .drop_foreign_key("fk_ticket_checked_in_by")
+                    .drop_column(Ticket::CheckedInAt)
+                    .drop_column(Ticket::CheckedInBy)
+                    .to_owned(),
+            )
+            .await
+    }
+}
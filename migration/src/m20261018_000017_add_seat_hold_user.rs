@@ -0,0 +1,58 @@
+use crate::theater::SeatHold;
+use crate::user::User;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Holds are short lived, so the ones placed before holds had a holder are dropped
+        let _ = manager
+            .exec_stmt(Query::delete().from_table(SeatHold::Table).to_owned())
+            .await?;
+
+        // Every hold belongs to the user who placed it
+        let _ = manager
+            .alter_table(
+                Table::alter()
+                    .table(SeatHold::Table)
+                    .add_column_if_not_exists(uuid(SeatHold::UserId).not_null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_seat_hold_user")
+                            .from_tbl(SeatHold::Table)
+                            .from_col(SeatHold::UserId)
+                            .to_tbl(User::Table)
+                            .to_col(User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Used to count the seats a user is holding
+        manager
+            .create_index(
+                IndexCreateStatement::new()
+                    .name("idx_seat_hold_user_id")
+                    .table(SeatHold::Table)
+                    .col(SeatHold::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SeatHold::Table)
+                    .drop_foreign_key("fk_seat_hold_user")
+                    .drop_column(SeatHold::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
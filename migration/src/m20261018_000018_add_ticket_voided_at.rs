@@ -0,0 +1,121 @@
+use crate::booking::Ticket;
+use crate::theater::TakenSeat;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // A ticket remembers the seat it was issued for, so it outlives the seat being released
+        let _ = manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .add_column_if_not_exists(uuid_null(Ticket::ShowtimeId))
+                    .add_column_if_not_exists(integer_null(Ticket::ShowtimeRoomId))
+                    .add_column_if_not_exists(string_len_null(Ticket::SeatIdentifier, 3))
+                    .add_column_if_not_exists(timestamp_with_time_zone_null(Ticket::VoidedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Copy the seats of the tickets issued so far
+        let _ = manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                UPDATE ticket t
+                SET showtime_id      = ts.showtime_id,
+                    showtime_room_id = ts.showtime_room_id,
+                    seat_identifier  = ts.seat_identifier
+                FROM taken_seat ts
+                WHERE ts.id = t.taken_seat_id
+                "#,
+            )
+            .await?;
+
+        let _ = manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .modify_column(uuid(Ticket::ShowtimeId).not_null())
+                    .modify_column(integer(Ticket::ShowtimeRoomId).not_null())
+                    .modify_column(string_len(Ticket::SeatIdentifier, 3).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Releasing a seat detaches its ticket instead of deleting it
+        let _ = manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .drop_foreign_key("fk_ticket_taken_seat")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .modify_column(integer_null(Ticket::TakenSeatId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_ticket_taken_seat")
+                            .from_tbl(Ticket::Table)
+                            .from_col(Ticket::TakenSeatId)
+                            .to_tbl(TakenSeat::Table)
+                            .to_col(TakenSeat::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Tickets whose seat was released cannot point at it anymore
+        let _ = manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Ticket::Table)
+                    .and_where(Expr::col(Ticket::TakenSeatId).is_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        let _ = manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .drop_foreign_key("fk_ticket_taken_seat")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .modify_column(integer(Ticket::TakenSeatId).not_null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_ticket_taken_seat")
+                            .from_tbl(Ticket::Table)
+                            .from_col(Ticket::TakenSeatId)
+                            .to_tbl(TakenSeat::Table)
+                            .to_col(TakenSeat::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .drop_column(Ticket::ShowtimeId)
+                    .drop_column(Ticket::ShowtimeRoomId)
+                    .drop_column(Ticket::SeatIdentifier)
+                    .drop_column(Ticket::VoidedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -9,4 +9,26 @@ pub enum Movie {
     Rating,
     Genre,
     PosterUrl,
+    RuntimeMinutes,
+    ReleaseDate,
+    AgeRating,
+    Language,
+    TrailerUrl,
+    Director,
+    CastMembers,
+    SearchVector,
+}
+
+#[derive(DeriveIden)]
+pub enum Genre {
+    Table,
+    Id,
+    Name,
+}
+
+#[derive(DeriveIden)]
+pub enum MovieGenre {
+    Table,
+    MovieId,
+    GenreId,
 }
@@ -6,6 +6,11 @@ pub enum Theater {
     Id,
     Name,
     Location,
+    Timezone,
+    Latitude,
+    Longitude,
+    Address,
+    City,
 }
 
 #[derive(DeriveIden)]
@@ -17,6 +22,7 @@ pub enum Room {
     MaxRows,
     MaxColumns,
     TheaterId,
+    Layout,
 }
 
 #[derive(DeriveIden)]
@@ -45,4 +51,18 @@ pub enum TakenSeat {
     ShowtimeId,
     ShowtimeRoomId,
     SeatIdentifier,
+    BookingId,
+}
+
+#[derive(DeriveIden)]
+pub enum SeatHold {
+    Table,
+    Id,
+    HoldId,
+    ShowtimeId,
+    ShowtimeRoomId,
+    SeatIdentifier,
+    ExpiresAt,
+    CreatedAt,
+    UserId,
 }
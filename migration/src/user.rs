@@ -0,0 +1,47 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveIden)]
+pub enum User {
+    Table,
+    Id,
+    Email,
+    Name,
+    PasswordHash,
+    Role,
+    TheaterId,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum UserSession {
+    Table,
+    Id,
+    UserId,
+    TokenHash,
+    ExpiresAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum RefreshToken {
+    Table,
+    Id,
+    UserId,
+    FamilyId,
+    TokenHash,
+    ExpiresAt,
+    RevokedAt,
+    ReplacedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum UserRole {
+    #[sea_orm(iden = "user_role")]
+    Enum,
+    Customer,
+    BoxOffice,
+    TheaterManager,
+    Admin,
+}
@@ -17,20 +17,32 @@ pub struct FieldError {
 #[derive(Debug, Error)]
 pub enum AppError {
     #[error("Validation")]
-    #[allow(dead_code)]
     Validation(Vec<FieldError>),
 
     #[error("Unauthorized: {0}")]
-    #[allow(dead_code)]
     Unauthorized(String),
 
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     #[error("BadRequest: {0}")]
-    #[allow(dead_code)]
     BadRequest(String),
 
     #[error("NotFound: {0}")]
     NotFound(String),
 
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("PaymentDeclined: {0}")]
+    PaymentDeclined(String),
+
+    #[error("PaymentUnavailable: {0}")]
+    PaymentUnavailable(String),
+
+    #[error("SeatsTaken: Some of the requested seats are already taken")]
+    SeatsTaken(Vec<String>),
+
     #[error("An unexpected internal error occured")]
     InternalServerError {
         #[from]
@@ -82,6 +94,8 @@ pub struct AppErrorResponse<'a> {
     pub message: &'a str,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub validation_errors: Option<&'a [FieldError]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub taken_seats: Option<&'a [String]>,
 }
 
 impl ResponseError for AppError {
@@ -89,7 +103,11 @@ impl ResponseError for AppError {
         match *self {
             Self::Validation(_) | Self::BadRequest(_) => StatusCode::BAD_REQUEST,
             Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
             Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Conflict(_) | Self::SeatsTaken(_) => StatusCode::CONFLICT,
+            Self::PaymentDeclined(_) => StatusCode::PAYMENT_REQUIRED,
+            Self::PaymentUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
 
             // All other errors are server-side issues.
             _ => StatusCode::INTERNAL_SERVER_ERROR,
@@ -102,7 +120,7 @@ impl ResponseError for AppError {
 
         let status_code = self.status_code();
 
-        let mut builder = HttpResponseBuilder::new(status_code.clone());
+        let mut builder = HttpResponseBuilder::new(status_code);
         builder.insert_header(header::ContentType(mime::APPLICATION_JSON));
 
         let message = self.get_error_message();
@@ -112,12 +130,21 @@ impl ResponseError for AppError {
                 code: status_code.as_u16(),
                 message: &message,
                 validation_errors: Some(errors),
+                taken_seats: None,
+            }),
+
+            Self::SeatsTaken(seats) => builder.json(AppErrorResponse {
+                code: status_code.as_u16(),
+                message: &message,
+                validation_errors: None,
+                taken_seats: Some(seats),
             }),
 
             _ => builder.json(AppErrorResponse {
                 code: status_code.as_u16(),
                 message: &message,
                 validation_errors: None,
+                taken_seats: None,
             }),
         }
     }
@@ -1,8 +1,12 @@
 use crate::app_error::AppError;
+use crate::config::Config;
+use crate::payments::PaymentGateway;
 use sea_orm::DatabaseConnection;
 
 pub struct AppState {
     pub database_connection: DatabaseConnection,
+    pub config: Config,
+    pub payment_gateway: PaymentGateway,
 }
 
 pub type Result<T> = core::result::Result<T, AppError>;
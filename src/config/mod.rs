@@ -3,16 +3,22 @@ pub mod db;
 use log::LevelFilter;
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize, Clone, PartialOrd, PartialEq)]
+#[derive(Debug, Deserialize, Clone, PartialOrd, PartialEq, Default)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum Environment {
+    #[default]
     Development,
     Production,
 }
 
-impl Default for Environment {
-    fn default() -> Self {
-        Self::Development
+/// Secrets shorter than this are easy enough to guess that tokens signed with them could be
+/// forged.
+const MIN_SECRET_LEN: usize = 32;
+
+/// Refuses to start with a secret that would let anyone forge what it signs.
+fn check_secret(name: &str, secret: &str) {
+    if secret.trim().len() < MIN_SECRET_LEN {
+        panic!("{name} must be at least {MIN_SECRET_LEN} characters long");
     }
 }
 
@@ -32,6 +38,71 @@ fn get_default_log_level() -> String {
     "info".to_string()
 }
 
+fn get_default_seat_hold_ttl_seconds() -> u64 {
+    600
+}
+
+fn get_default_seat_hold_reaper_interval_seconds() -> u64 {
+    60
+}
+
+fn get_default_seat_hold_max_seats_per_user() -> u64 {
+    10
+}
+
+fn get_default_booking_cancellation_cutoff_minutes() -> i64 {
+    60
+}
+
+fn get_default_showtime_cleaning_buffer_minutes() -> i64 {
+    15
+}
+
+fn get_default_access_token_ttl_minutes() -> i64 {
+    15
+}
+
+fn get_default_refresh_token_ttl_days() -> i64 {
+    30
+}
+
+fn get_default_check_in_opens_minutes() -> i64 {
+    60
+}
+
+fn get_default_check_in_closes_minutes() -> i64 {
+    30
+}
+
+fn get_default_payment_timeout_seconds() -> u64 {
+    10
+}
+
+fn get_default_pending_booking_timeout_minutes() -> i64 {
+    15
+}
+
+fn get_default_pending_booking_reaper_interval_seconds() -> u64 {
+    60
+}
+
+/// How the built-in mock payment provider settles payments that do not ask for an outcome.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MockPaymentOutcome {
+    #[default]
+    Succeed,
+    Decline,
+    Timeout,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PaymentProviderKind {
+    #[default]
+    Mock,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct Config {
     #[serde(default = "get_default_env")]
@@ -43,13 +114,51 @@ pub struct Config {
     #[serde(default = "get_default_host")]
     pub host: String,
     pub database_url: String,
+    #[serde(default = "get_default_seat_hold_ttl_seconds")]
+    pub seat_hold_ttl_seconds: u64,
+    #[serde(default = "get_default_seat_hold_reaper_interval_seconds")]
+    pub seat_hold_reaper_interval_seconds: u64,
+    #[serde(default = "get_default_seat_hold_max_seats_per_user")]
+    pub seat_hold_max_seats_per_user: u64,
+    #[serde(default = "get_default_booking_cancellation_cutoff_minutes")]
+    pub booking_cancellation_cutoff_minutes: i64,
+    #[serde(default = "get_default_showtime_cleaning_buffer_minutes")]
+    pub showtime_cleaning_buffer_minutes: i64,
+    /// Secret access tokens are signed with, at least `MIN_SECRET_LEN` characters long
+    pub jwt_secret: String,
+    #[serde(default = "get_default_access_token_ttl_minutes")]
+    pub access_token_ttl_minutes: i64,
+    #[serde(default = "get_default_refresh_token_ttl_days")]
+    pub refresh_token_ttl_days: i64,
+    #[serde(default)]
+    pub payment_provider: PaymentProviderKind,
+    #[serde(default = "get_default_payment_timeout_seconds")]
+    pub payment_timeout_seconds: u64,
+    #[serde(default = "get_default_pending_booking_timeout_minutes")]
+    pub pending_booking_timeout_minutes: i64,
+    #[serde(default = "get_default_pending_booking_reaper_interval_seconds")]
+    pub pending_booking_reaper_interval_seconds: u64,
+    #[serde(default)]
+    pub mock_payment_outcome: MockPaymentOutcome,
+    /// Shared secret payment webhooks are signed with, webhooks are refused while it is unset
+    pub payment_webhook_secret: Option<String>,
+    /// Secret ticket tokens are signed with, at least `MIN_SECRET_LEN` characters long
+    pub ticket_signing_secret: String,
+    #[serde(default = "get_default_check_in_opens_minutes")]
+    pub check_in_opens_minutes: i64,
+    #[serde(default = "get_default_check_in_closes_minutes")]
+    pub check_in_closes_minutes: i64,
 }
 
 impl Config {
     pub fn new() -> Self {
         dotenvy::dotenv().expect("Failed to load env variables from .env");
-        envy::from_env()
-            .expect("There are missing or invalid value for Config read from env variables")
+        let config: Self = envy::from_env()
+            .expect("There are missing or invalid value for Config read from env variables");
+        check_secret("JWT_SECRET", &config.jwt_secret);
+        check_secret("TICKET_SIGNING_SECRET", &config.ticket_signing_secret);
+
+        config
     }
 
     pub fn rust_log_to_level_filter(&self) -> LevelFilter {
@@ -0,0 +1,71 @@
+use actix_web::{FromRequest, HttpMessage, HttpRequest, dev::Payload};
+use entity::sea_orm_active_enums::UserRole;
+use std::future::{Ready, ready};
+use uuid::Uuid;
+
+use crate::{app_error::AppError, app_state::Result};
+
+/// The user making the request, put in the request extensions by the authentication
+/// middleware. Handlers that take this as an argument respond with 401 for anonymous requests.
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser {
+    pub id: Uuid,
+    pub role: UserRole,
+    pub theater_id: Option<Uuid>,
+}
+
+impl AuthenticatedUser {
+    /// Responds with 403 unless the user has one of `roles`.
+    pub fn require_role(&self, roles: &[UserRole]) -> Result<()> {
+        if roles.contains(&self.role) {
+            Ok(())
+        } else {
+            Err(AppError::Forbidden(
+                "You are not allowed to perform this action".to_string(),
+            ))
+        }
+    }
+
+    /// Admins can administer every theater, theater managers only the one they belong to.
+    pub fn require_theater_manager(&self, theater_id: Uuid) -> Result<()> {
+        match self.role {
+            UserRole::Admin => Ok(()),
+            UserRole::TheaterManager if self.theater_id == Some(theater_id) => Ok(()),
+            _ => Err(AppError::Forbidden(format!(
+                "You are not allowed to administer theater with id: {}",
+                theater_id
+            ))),
+        }
+    }
+
+    /// Admins work at every theater, box office staff and theater managers only at the one they
+    /// belong to.
+    pub fn require_theater_staff(&self, theater_id: Uuid) -> Result<()> {
+        match self.role {
+            UserRole::Admin => Ok(()),
+            UserRole::BoxOffice | UserRole::TheaterManager
+                if self.theater_id == Some(theater_id) =>
+            {
+                Ok(())
+            }
+            _ => Err(AppError::Forbidden(format!(
+                "You are not staff of theater with id: {}",
+                theater_id
+            ))),
+        }
+    }
+}
+
+impl FromRequest for AuthenticatedUser {
+    type Error = AppError;
+    type Future = Ready<std::result::Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(
+            req.extensions()
+                .get::<AuthenticatedUser>()
+                .cloned()
+                .ok_or_else(|| AppError::Unauthorized("Missing bearer token".to_string())),
+        )
+    }
+}
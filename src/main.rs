@@ -1,16 +1,23 @@
 mod app_error;
 mod app_state;
 mod config;
+mod extractors;
+mod middlewares;
 mod models;
+mod payments;
 mod routes;
 mod services;
 
 use crate::app_state::AppState;
 use crate::config::Config;
 use crate::config::db::get_database_connection;
+use crate::payments::PaymentGateway;
+use crate::services::booking_service::spawn_pending_booking_reaper;
+use crate::services::seat_hold_service::spawn_seat_hold_reaper;
 use actix_web::middleware::{Logger, NormalizePath};
 use actix_web::{App, HttpResponse, HttpServer, get, http::StatusCode, main, web};
 use serde_json::json;
+use std::time::Duration;
 
 #[get("/")]
 async fn hello_world() -> HttpResponse {
@@ -26,9 +33,23 @@ async fn main() -> std::io::Result<()> {
     config.setup_log();
 
     let database_connection = get_database_connection(&config).await;
+    let payment_gateway = PaymentGateway::new(&config);
+
+    spawn_seat_hold_reaper(
+        database_connection.clone(),
+        Duration::from_secs(config.seat_hold_reaper_interval_seconds),
+    );
+    spawn_pending_booking_reaper(
+        database_connection.clone(),
+        payment_gateway.clone(),
+        Duration::from_secs(config.pending_booking_reaper_interval_seconds),
+        chrono::Duration::minutes(config.pending_booking_timeout_minutes),
+    );
 
     let app_state = web::Data::new(AppState {
         database_connection,
+        payment_gateway,
+        config: config.clone(),
     });
 
     HttpServer::new(move || {
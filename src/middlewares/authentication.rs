@@ -0,0 +1,43 @@
+use actix_web::{
+    HttpMessage,
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    http::header,
+    middleware::Next,
+    web::Data,
+};
+use anyhow::anyhow;
+
+use crate::{
+    app_error::AppError, app_state::AppState, services::auth_service::decode_access_token,
+};
+
+/// Validates the access token of requests that carry an `Authorization` header and makes the
+/// user available to the `AuthenticatedUser` extractor. Requests without the header pass
+/// through untouched, so public endpoints keep working for anonymous users.
+pub async fn authentication(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    if let Some(authorization) = req.headers().get(header::AUTHORIZATION) {
+        let app_state = req
+            .app_data::<Data<AppState>>()
+            .ok_or_else(|| AppError::from(anyhow!("AppState is not registered")))?;
+
+        let token = authorization
+            .to_str()
+            .ok()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| {
+                AppError::Unauthorized("Authorization header must be a bearer token".to_string())
+            })?;
+
+        let user = decode_access_token(&app_state.config, token)?;
+
+        req.extensions_mut().insert(user);
+    }
+
+    next.call(req).await
+}
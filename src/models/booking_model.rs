@@ -0,0 +1,54 @@
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Booking {
+    pub id: String,
+    pub showtime_id: String,
+    pub showtime_room_id: i32,
+    pub seats: Vec<String>,
+    pub amount: i32,
+    pub status: String,
+    pub refund_status: String,
+    pub cancellation_reason: Option<String>,
+    pub cancelled_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookedMovie {
+    pub id: String,
+    pub title: String,
+    pub poster_url: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookedTheater {
+    pub id: String,
+    pub name: String,
+    pub location: String,
+    pub timezone: String,
+}
+
+/// A booking together with what was booked, as listed in the customer's booking history.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookingHistory {
+    pub id: String,
+    pub showtime_id: String,
+    pub showtime_room_id: i32,
+    pub movie: BookedMovie,
+    pub theater: BookedTheater,
+    pub room_name: String,
+    pub time: DateTime<Utc>,
+    pub local_time: DateTime<FixedOffset>,
+    pub seats: Vec<String>,
+    pub amount: i32,
+    pub status: String,
+    pub refund_status: String,
+    pub cancelled_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,7 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct Genre {
+    pub id: i32,
+    pub name: String,
+}
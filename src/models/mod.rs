@@ -1,4 +1,12 @@
+pub mod booking_model;
+pub mod genre_model;
 pub mod movie_model;
 pub mod requests;
+pub mod room_layout_model;
+pub mod room_model;
+pub mod seat_hold_model;
+pub mod seat_map_model;
 pub mod showtime_model;
 pub mod theater_model;
+pub mod ticket_model;
+pub mod user_model;
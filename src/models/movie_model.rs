@@ -1,3 +1,4 @@
+use chrono::NaiveDate;
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
@@ -7,6 +8,13 @@ pub struct Movie {
     pub title: String,
     pub overview: String,
     pub rating: f32,
-    pub genre: String,
+    pub genres: Vec<String>,
     pub poster_url: String,
+    pub runtime_minutes: Option<i32>,
+    pub release_date: Option<NaiveDate>,
+    pub age_rating: Option<String>,
+    pub language: Option<String>,
+    pub trailer_url: Option<String>,
+    pub director: Option<String>,
+    pub cast: Vec<String>,
 }
@@ -0,0 +1,6 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct CancelBookingRequest {
+    pub reason: Option<String>,
+}
@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct CheckInRequest {
+    /// The token encoded in the ticket's QR code
+    pub token: String,
+}
@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBookingRequest {
+    pub seats: Vec<String>,
+    /// The hold the seats were reserved under, if any.
+    pub hold_id: Option<String>,
+    /// Token of what the customer pays with, as issued by the payment provider.
+    pub payment_method: Option<String>,
+}
@@ -0,0 +1,19 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMovieRequest {
+    pub title: String,
+    pub overview: String,
+    pub rating: f32,
+    pub genres: Vec<String>,
+    pub poster_url: String,
+    pub runtime_minutes: Option<i32>,
+    pub release_date: Option<NaiveDate>,
+    pub age_rating: Option<String>,
+    pub language: Option<String>,
+    pub trailer_url: Option<String>,
+    pub director: Option<String>,
+    pub cast: Option<Vec<String>>,
+}
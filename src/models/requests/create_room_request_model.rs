@@ -0,0 +1,15 @@
+use serde::Deserialize;
+
+use crate::models::room_layout_model::RoomLayout;
+
+/// `maxRows` and `maxColumns` can be left out when the room has a layout, since they follow
+/// from it.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRoomRequest {
+    pub name: String,
+    pub capacity: i32,
+    pub max_rows: Option<i32>,
+    pub max_columns: Option<i32>,
+    pub layout: Option<RoomLayout>,
+}
@@ -0,0 +1,6 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSeatHoldRequest {
+    pub seats: Vec<String>,
+}
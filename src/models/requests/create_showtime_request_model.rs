@@ -0,0 +1,23 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShowtimeSlotRequest {
+    pub room_id: String,
+    /// Wall-clock time in the theater's timezone such as `2026-10-25T19:30`, or an instant with
+    /// an offset such as `2026-10-25T12:30:00Z`
+    pub time: String,
+    pub price: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateShowtimeRequest {
+    pub movie_id: String,
+    pub slots: Vec<ShowtimeSlotRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddShowtimeSlotsRequest {
+    pub slots: Vec<ShowtimeSlotRequest>,
+}
@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTheaterRequest {
+    pub name: String,
+    pub location: String,
+    /// IANA timezone the theater's showtimes are scheduled in, `UTC` when left out
+    pub timezone: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub address: Option<String>,
+    pub city: Option<String>,
+}
@@ -2,7 +2,7 @@ use serde::Deserialize;
 
 fn parse_direction_to_sea_orm_order(
     direction: &str,
-) -> Result<sea_orm::Order, serde::de::Unexpected> {
+) -> Result<sea_orm::Order, serde::de::Unexpected<'_>> {
     match direction {
         "asc" => Ok(sea_orm::Order::Asc),
         "desc" => Ok(sea_orm::Order::Desc),
@@ -86,7 +86,10 @@ fn get_default_page() -> u64 {
     1
 }
 
+/// Filters are kept as raw strings so that malformed values are reported as validation errors
+/// by the movies service instead of being rejected by the query extractor.
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetMoviesQueryParams {
     #[serde(default = "get_default_limit")]
     pub limit: u64,
@@ -94,4 +97,18 @@ pub struct GetMoviesQueryParams {
     pub page: u64,
     #[serde(rename = "sort", default = "get_default_sort_options")]
     pub sort_options: SortTuple,
+    /// Comma separated genre names, matching movies filed under any of them
+    pub genre: Option<String>,
+    pub min_rating: Option<String>,
+    pub max_rating: Option<String>,
+    /// Earliest release date, as `YYYY-MM-DD`
+    pub released_from: Option<String>,
+    /// Latest release date, as `YYYY-MM-DD`
+    pub released_to: Option<String>,
+    /// Only movies with an upcoming showtime slot
+    pub now_showing: Option<String>,
+    /// Only movies releasing after today
+    pub coming_soon: Option<String>,
+    /// `nextCursor` of the previous response, continuing after it instead of using `page`
+    pub after: Option<String>,
 }
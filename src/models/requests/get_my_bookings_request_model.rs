@@ -0,0 +1,21 @@
+use serde::Deserialize;
+
+fn get_default_limit() -> u64 {
+    20
+}
+
+fn get_default_page() -> u64 {
+    1
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMyBookingsQueryParams {
+    #[serde(default = "get_default_limit")]
+    pub limit: u64,
+    #[serde(default = "get_default_page")]
+    pub page: u64,
+    /// `upcoming` for bookings whose slot has not started yet, soonest first, or `past` for
+    /// the others, latest first. Both are listed, latest first, when left out.
+    pub when: Option<String>,
+}
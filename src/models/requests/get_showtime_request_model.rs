@@ -0,0 +1,31 @@
+use serde::Deserialize;
+
+fn get_default_limit() -> u64 {
+    20
+}
+
+fn get_default_page() -> u64 {
+    1
+}
+
+/// Filters are kept as raw strings so that malformed values are reported as validation errors
+/// by the showtime service instead of being rejected by the query extractor.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetShowtimeQueryParams {
+    #[serde(default = "get_default_limit")]
+    pub limit: u64,
+    #[serde(default = "get_default_page")]
+    pub page: u64,
+    /// Earliest slot time, as `YYYY-MM-DD`, `YYYY-MM-DDTHH:MM[:SS]` or an RFC 3339 time with an
+    /// offset. Times without an offset are UTC.
+    pub from: Option<String>,
+    /// Latest slot time, in the same formats as `from`. A date includes the whole day.
+    pub to: Option<String>,
+    pub movie_id: Option<String>,
+    pub theater_id: Option<String>,
+    /// Matched against the theater city, or its location when the theater has no city
+    pub city: Option<String>,
+    pub min_price: Option<String>,
+    pub max_price: Option<String>,
+}
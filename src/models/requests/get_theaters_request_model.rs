@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+/// Filters are kept as raw strings so that malformed values are reported as validation errors
+/// by the theaters service instead of being rejected by the query extractor.
+#[derive(Debug, Deserialize)]
+pub struct GetTheatersQueryParams {
+    /// `latitude,longitude` to order the theaters by distance from, leaving out theaters that
+    /// are not on the map
+    pub near: Option<String>,
+    /// Only theaters within this many kilometres of `near`
+    pub radius_km: Option<String>,
+    /// Include this many upcoming showtime slots of each theater
+    pub next_showtimes: Option<String>,
+}
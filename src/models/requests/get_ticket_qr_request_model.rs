@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct GetTicketQrQueryParams {
+    /// `png` or `svg`, defaults to `png`
+    pub format: Option<String>,
+}
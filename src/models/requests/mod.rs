@@ -1 +1,22 @@
+pub mod cancel_booking_request_model;
+pub mod check_in_request_model;
+pub mod create_booking_request_model;
+pub mod create_movie_request_model;
+pub mod create_room_request_model;
+pub mod create_seat_hold_request_model;
+pub mod create_showtime_request_model;
+pub mod create_theater_request_model;
 pub mod get_movies_request_model;
+pub mod get_my_bookings_request_model;
+pub mod get_showtime_request_model;
+pub mod get_theaters_request_model;
+pub mod get_ticket_qr_request_model;
+pub mod login_request_model;
+pub mod payment_webhook_request_model;
+pub mod refresh_token_request_model;
+pub mod register_request_model;
+pub mod search_movies_request_model;
+pub mod update_movie_request_model;
+pub mod update_room_request_model;
+pub mod update_theater_request_model;
+pub mod update_user_role_request_model;
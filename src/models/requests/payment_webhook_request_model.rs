@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentWebhookData {
+    pub intent_id: String,
+}
+
+/// An event reported by the payment provider, signed with the webhook secret.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentWebhookEvent {
+    /// Unique per event, a delivery with an id that was seen before is a replay
+    pub id: String,
+    /// `payment.captured`, `payment.failed` or `payment.refunded`. Other types are recorded
+    /// and otherwise ignored.
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub created_at: DateTime<Utc>,
+    pub data: PaymentWebhookData,
+}
@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+fn get_default_limit() -> u64 {
+    10
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchMoviesQueryParams {
+    #[serde(default)]
+    pub q: String,
+    #[serde(default = "get_default_limit")]
+    pub limit: u64,
+}
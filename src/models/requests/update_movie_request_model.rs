@@ -0,0 +1,21 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+/// Fields left out of the request keep their current value. Optional text fields are cleared
+/// by sending an empty string.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateMovieRequest {
+    pub title: Option<String>,
+    pub overview: Option<String>,
+    pub rating: Option<f32>,
+    pub genres: Option<Vec<String>>,
+    pub poster_url: Option<String>,
+    pub runtime_minutes: Option<i32>,
+    pub release_date: Option<NaiveDate>,
+    pub age_rating: Option<String>,
+    pub language: Option<String>,
+    pub trailer_url: Option<String>,
+    pub director: Option<String>,
+    pub cast: Option<Vec<String>>,
+}
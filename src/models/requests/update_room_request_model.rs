@@ -0,0 +1,15 @@
+use serde::Deserialize;
+
+use crate::models::room_layout_model::RoomLayout;
+
+/// Fields left out of the request keep their current value. Changing `maxRows` or
+/// `maxColumns` without sending a layout turns the room back into a plain rectangle.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRoomRequest {
+    pub name: Option<String>,
+    pub capacity: Option<i32>,
+    pub max_rows: Option<i32>,
+    pub max_columns: Option<i32>,
+    pub layout: Option<RoomLayout>,
+}
@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+/// Fields left out of the request keep their current value. The address and city are cleared
+/// by sending an empty string.
+#[derive(Debug, Deserialize)]
+pub struct UpdateTheaterRequest {
+    pub name: Option<String>,
+    pub location: Option<String>,
+    pub timezone: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub address: Option<String>,
+    pub city: Option<String>,
+}
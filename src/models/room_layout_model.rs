@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use super::seat_map_model::SeatKind;
+
+/// One string per row, starting from the front of the room. Every character is a grid
+/// position: `S` is a seat, `W` a wheelchair space, `_` an aisle and `.` a position without
+/// anything in it. Rows do not need to have the same length.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RoomLayout {
+    pub rows: Vec<String>,
+}
+
+impl RoomLayout {
+    pub fn grid(&self) -> Result<Vec<Vec<SeatKind>>, String> {
+        self.rows
+            .iter()
+            .enumerate()
+            .map(|(row_index, row)| {
+                row.chars()
+                    .map(|position| match position {
+                        'S' => Ok(SeatKind::Seat),
+                        'W' => Ok(SeatKind::Wheelchair),
+                        '_' => Ok(SeatKind::Aisle),
+                        '.' => Ok(SeatKind::Gap),
+                        _ => Err(format!(
+                            "Row {} contains '{}', expected one of 'S', 'W', '_' or '.'",
+                            row_index + 1,
+                            position
+                        )),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,15 @@
+use serde::Serialize;
+
+use super::room_layout_model::RoomLayout;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Room {
+    pub id: String,
+    pub theater_id: String,
+    pub name: String,
+    pub capacity: i32,
+    pub max_rows: i32,
+    pub max_columns: i32,
+    pub layout: Option<RoomLayout>,
+}
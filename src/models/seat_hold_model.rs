@@ -0,0 +1,12 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeatHold {
+    pub hold_id: String,
+    pub showtime_id: String,
+    pub showtime_room_id: i32,
+    pub seats: Vec<String>,
+    pub expires_at: NaiveDateTime,
+}
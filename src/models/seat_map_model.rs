@@ -0,0 +1,50 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SeatKind {
+    Seat,
+    Wheelchair,
+    Aisle,
+    Gap,
+}
+
+impl SeatKind {
+    pub fn is_seat(&self) -> bool {
+        matches!(self, Self::Seat | Self::Wheelchair)
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SeatStatus {
+    Available,
+    Held,
+    Sold,
+    Blocked,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Seat {
+    /// Only seats and wheelchair spaces have an identifier and a status.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub row: String,
+    pub column: u32,
+    pub kind: SeatKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<SeatStatus>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeatMap {
+    pub showtime_id: String,
+    pub showtime_room_id: i32,
+    pub room_id: String,
+    pub room_name: String,
+    pub capacity: i32,
+    pub max_rows: i32,
+    pub max_columns: i32,
+    pub rows: Vec<Vec<Seat>>,
+}
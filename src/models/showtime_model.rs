@@ -1,10 +1,13 @@
-use chrono::NaiveDateTime;
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
 use serde::Serialize;
 
 #[derive(Debug, Serialize, Clone)]
 pub struct ShowtimeRoom {
     pub id: u64,
-    pub time: NaiveDateTime,
+    pub time: DateTime<Utc>,
+    /// Wall-clock time in the theater's timezone, with its UTC offset
+    pub local_time: DateTime<FixedOffset>,
+    pub timezone: String,
     pub price: u32,
     pub room_id: String,
     pub room_name: String,
@@ -16,7 +19,7 @@ pub struct Movie {
     pub id: String,
     pub title: String,
     pub rating: f64,
-    pub genre: String,
+    pub genres: Vec<String>,
     pub poster_url: String,
 }
 
@@ -25,6 +28,7 @@ pub struct Theater {
     pub id: String,
     pub name: String,
     pub location: String,
+    pub timezone: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -42,3 +46,9 @@ pub struct Showtime {
 
     pub theaters: Vec<Theater>,
 }
+
+#[derive(Debug, Serialize)]
+pub struct TakenSeats {
+    pub sold: Vec<String>,
+    pub held: Vec<String>,
+}
@@ -1,8 +1,32 @@
+use chrono::{DateTime, FixedOffset, Utc};
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NextShowtime {
+    pub showtime_id: String,
+    pub showtime_room_id: u64,
+    pub movie_id: String,
+    pub movie_title: String,
+    pub room_name: String,
+    pub time: DateTime<Utc>,
+    pub local_time: DateTime<FixedOffset>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Theater {
     pub id: String,
     pub name: String,
     pub location: String,
+    pub timezone: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    /// Great-circle distance from the point the theaters were searched near
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance_km: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_showtimes: Option<Vec<NextShowtime>>,
 }
@@ -0,0 +1,33 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ticket {
+    pub id: String,
+    pub booking_id: String,
+    pub showtime_id: String,
+    pub showtime_room_id: i32,
+    pub seat: String,
+    /// Signed token the ticket's QR code encodes, checked at the door
+    pub token: String,
+    pub checked_in_at: Option<DateTime<Utc>>,
+    /// Set once the booking is cancelled or refunded, the ticket is no longer let in
+    pub voided_at: Option<DateTime<Utc>>,
+}
+
+/// A ticket that was let in at the door.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckIn {
+    pub ticket_id: String,
+    pub booking_id: String,
+    pub showtime_id: String,
+    pub showtime_room_id: i32,
+    pub seat: String,
+    pub movie_title: String,
+    pub room_name: String,
+    pub time: DateTime<Utc>,
+    pub local_time: DateTime<FixedOffset>,
+    pub checked_in_at: DateTime<FixedOffset>,
+}
@@ -0,0 +1,29 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    pub id: String,
+    pub email: String,
+    pub name: String,
+    pub role: String,
+    pub theater_id: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthTokens {
+    pub access_token: String,
+    pub access_token_expires_at: NaiveDateTime,
+    pub refresh_token: String,
+    pub refresh_token_expires_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Session {
+    #[serde(flatten)]
+    pub tokens: AuthTokens,
+    pub user: User,
+}
@@ -0,0 +1,189 @@
+use async_trait::async_trait;
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use crate::config::MockPaymentOutcome;
+
+use super::{CreatePaymentIntent, PaymentError, PaymentIntent, PaymentProvider, PaymentStatus};
+
+/// Payment methods that make the mock settle a payment a certain way regardless of the
+/// configured outcome.
+const SUCCEED_PAYMENT_METHOD: &str = "mock_succeed";
+const DECLINE_PAYMENT_METHOD: &str = "mock_decline";
+const TIMEOUT_PAYMENT_METHOD: &str = "mock_timeout";
+const DECLINE_CAPTURE_PAYMENT_METHOD: &str = "mock_decline_capture";
+const TIMEOUT_CAPTURE_PAYMENT_METHOD: &str = "mock_timeout_capture";
+const FAIL_REFUND_PAYMENT_METHOD: &str = "mock_fail_refund";
+
+/// Long enough for any caller to give up waiting.
+const TIMEOUT_DELAY: Duration = Duration::from_secs(60 * 60);
+
+/// A step after authorisation that the mock fails for an intent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MockFailure {
+    DeclineCapture,
+    TimeoutCapture,
+    FailRefund,
+}
+
+struct MockIntent {
+    intent: PaymentIntent,
+    failure: Option<MockFailure>,
+}
+
+/// In-memory payment provider for development and tests. Intents are lost on restart.
+pub struct MockPaymentProvider {
+    outcome: MockPaymentOutcome,
+    intents: Mutex<HashMap<String, MockIntent>>,
+}
+
+impl MockPaymentProvider {
+    pub fn new(outcome: MockPaymentOutcome) -> Self {
+        Self {
+            outcome,
+            intents: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn update_intent(
+        &self,
+        intent_id: &str,
+        update: impl FnOnce(&mut PaymentIntent) -> Result<(), PaymentError>,
+    ) -> Result<PaymentIntent, PaymentError> {
+        let mut intents = self.intents.lock().unwrap();
+        let intent = &mut intents
+            .get_mut(intent_id)
+            .ok_or_else(|| PaymentError::NotFound(intent_id.to_string()))?
+            .intent;
+
+        update(intent)?;
+
+        Ok(intent.clone())
+    }
+
+    fn failure(&self, intent_id: &str) -> Option<MockFailure> {
+        self.intents
+            .lock()
+            .unwrap()
+            .get(intent_id)
+            .and_then(|intent| intent.failure)
+    }
+
+    #[cfg(test)]
+    pub fn intent_status(&self, intent_id: &str) -> Option<PaymentStatus> {
+        self.intents
+            .lock()
+            .unwrap()
+            .get(intent_id)
+            .map(|intent| intent.intent.status)
+    }
+}
+
+#[async_trait]
+impl PaymentProvider for MockPaymentProvider {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn create_intent(
+        &self,
+        request: CreatePaymentIntent,
+    ) -> Result<PaymentIntent, PaymentError> {
+        let (outcome, failure) = match request.payment_method.as_deref() {
+            None => (self.outcome, None),
+            Some(SUCCEED_PAYMENT_METHOD) => (MockPaymentOutcome::Succeed, None),
+            Some(DECLINE_PAYMENT_METHOD) => (MockPaymentOutcome::Decline, None),
+            Some(TIMEOUT_PAYMENT_METHOD) => (MockPaymentOutcome::Timeout, None),
+            Some(DECLINE_CAPTURE_PAYMENT_METHOD) => (
+                MockPaymentOutcome::Succeed,
+                Some(MockFailure::DeclineCapture),
+            ),
+            Some(TIMEOUT_CAPTURE_PAYMENT_METHOD) => (
+                MockPaymentOutcome::Succeed,
+                Some(MockFailure::TimeoutCapture),
+            ),
+            Some(FAIL_REFUND_PAYMENT_METHOD) => {
+                (MockPaymentOutcome::Succeed, Some(MockFailure::FailRefund))
+            }
+            Some(payment_method) => {
+                return Err(PaymentError::Declined(format!(
+                    "{payment_method} is not a mock payment method"
+                )));
+            }
+        };
+
+        if outcome == MockPaymentOutcome::Timeout {
+            actix_web::rt::time::sleep(TIMEOUT_DELAY).await;
+            return Err(PaymentError::Timeout);
+        }
+
+        let intent = PaymentIntent {
+            id: format!("mock_pi_{}", request.booking_id.simple()),
+            amount: request.amount,
+            status: match outcome {
+                MockPaymentOutcome::Decline => PaymentStatus::Declined,
+                _ => PaymentStatus::RequiresCapture,
+            },
+        };
+
+        self.intents.lock().unwrap().insert(
+            intent.id.to_owned(),
+            MockIntent {
+                intent: intent.clone(),
+                failure,
+            },
+        );
+
+        if intent.status == PaymentStatus::Declined {
+            return Err(PaymentError::Declined("Card was declined".to_string()));
+        }
+
+        Ok(intent)
+    }
+
+    async fn capture(&self, intent_id: &str) -> Result<PaymentIntent, PaymentError> {
+        match self.failure(intent_id) {
+            Some(MockFailure::DeclineCapture) => {
+                return Err(PaymentError::Declined(
+                    "Card was declined on capture".to_string(),
+                ));
+            }
+            Some(MockFailure::TimeoutCapture) => {
+                actix_web::rt::time::sleep(TIMEOUT_DELAY).await;
+                return Err(PaymentError::Timeout);
+            }
+            _ => {}
+        }
+
+        self.update_intent(intent_id, |intent| match intent.status {
+            PaymentStatus::RequiresCapture | PaymentStatus::Captured => {
+                intent.status = PaymentStatus::Captured;
+                Ok(())
+            }
+            status => Err(PaymentError::InvalidState {
+                id: intent.id.to_owned(),
+                action: "captured",
+                status,
+            }),
+        })
+    }
+
+    async fn refund(&self, intent_id: &str) -> Result<PaymentIntent, PaymentError> {
+        if self.failure(intent_id) == Some(MockFailure::FailRefund) {
+            return Err(PaymentError::Provider(anyhow::anyhow!(
+                "Mock refund of {intent_id} failed"
+            )));
+        }
+
+        self.update_intent(intent_id, |intent| match intent.status {
+            PaymentStatus::RequiresCapture | PaymentStatus::Captured | PaymentStatus::Refunded => {
+                intent.status = PaymentStatus::Refunded;
+                Ok(())
+            }
+            status => Err(PaymentError::InvalidState {
+                id: intent.id.to_owned(),
+                action: "refunded",
+                status,
+            }),
+        })
+    }
+}
@@ -0,0 +1,143 @@
+pub mod mock_payment_provider;
+
+use async_trait::async_trait;
+use std::{future::Future, sync::Arc, time::Duration};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    app_error::AppError,
+    config::{Config, PaymentProviderKind},
+};
+
+use mock_payment_provider::MockPaymentProvider;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentStatus {
+    /// The amount is reserved on the customer's payment method but not charged yet
+    RequiresCapture,
+    Captured,
+    Declined,
+    Refunded,
+}
+
+#[derive(Debug, Clone)]
+pub struct PaymentIntent {
+    pub id: String,
+    pub amount: i32,
+    pub status: PaymentStatus,
+}
+
+#[derive(Debug)]
+pub struct CreatePaymentIntent {
+    pub booking_id: Uuid,
+    pub amount: i32,
+    /// Provider specific token of what the customer pays with
+    pub payment_method: Option<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum PaymentError {
+    #[error("Payment was declined: {0}")]
+    Declined(String),
+
+    #[error("Payment provider did not respond in time")]
+    Timeout,
+
+    #[error("Payment intent with id: {0} does not exist")]
+    NotFound(String),
+
+    #[error("Payment intent with id: {id} cannot be {action} while it is {status:?}")]
+    InvalidState {
+        id: String,
+        action: &'static str,
+        status: PaymentStatus,
+    },
+
+    #[error("Payment provider error: {0}")]
+    Provider(#[from] anyhow::Error),
+}
+
+impl From<PaymentError> for AppError {
+    fn from(err: PaymentError) -> Self {
+        match err {
+            PaymentError::Declined(reason) => AppError::PaymentDeclined(reason),
+            PaymentError::Timeout => AppError::PaymentUnavailable(err.to_string()),
+            err => AppError::InternalServerError { source: err.into() },
+        }
+    }
+}
+
+#[async_trait]
+pub trait PaymentProvider: Send + Sync {
+    /// Stored with each booking to know which provider its payment intent belongs to.
+    fn name(&self) -> &'static str;
+
+    /// Reserves the amount on the customer's payment method without charging it.
+    async fn create_intent(
+        &self,
+        request: CreatePaymentIntent,
+    ) -> Result<PaymentIntent, PaymentError>;
+
+    /// Charges a reserved amount. Capturing an intent that is already captured is a no-op.
+    async fn capture(&self, intent_id: &str) -> Result<PaymentIntent, PaymentError>;
+
+    /// Pays a captured amount back, or releases the reservation of an intent that was never
+    /// captured.
+    async fn refund(&self, intent_id: &str) -> Result<PaymentIntent, PaymentError>;
+}
+
+/// The configured payment provider, giving up on calls that take longer than the timeout.
+#[derive(Clone)]
+pub struct PaymentGateway {
+    provider: Arc<dyn PaymentProvider>,
+    timeout: Duration,
+}
+
+impl PaymentGateway {
+    pub fn new(config: &Config) -> Self {
+        let provider: Arc<dyn PaymentProvider> = match config.payment_provider {
+            PaymentProviderKind::Mock => {
+                Arc::new(MockPaymentProvider::new(config.mock_payment_outcome))
+            }
+        };
+
+        Self::with_provider(
+            provider,
+            Duration::from_secs(config.payment_timeout_seconds),
+        )
+    }
+
+    pub fn with_provider(provider: Arc<dyn PaymentProvider>, timeout: Duration) -> Self {
+        Self { provider, timeout }
+    }
+
+    async fn with_timeout<T>(
+        &self,
+        call: impl Future<Output = Result<T, PaymentError>>,
+    ) -> Result<T, PaymentError> {
+        actix_web::rt::time::timeout(self.timeout, call)
+            .await
+            .unwrap_or(Err(PaymentError::Timeout))
+    }
+
+    pub fn provider_name(&self) -> &'static str {
+        self.provider.name()
+    }
+
+    pub async fn create_intent(
+        &self,
+        request: CreatePaymentIntent,
+    ) -> Result<PaymentIntent, PaymentError> {
+        self.with_timeout(self.provider.create_intent(request))
+            .await
+    }
+
+    pub async fn capture(&self, intent_id: &str) -> Result<PaymentIntent, PaymentError> {
+        self.with_timeout(self.provider.capture(intent_id)).await
+    }
+
+    pub async fn refund(&self, intent_id: &str) -> Result<PaymentIntent, PaymentError> {
+        self.with_timeout(self.provider.refund(intent_id)).await
+    }
+}
@@ -0,0 +1,78 @@
+use actix_web::{
+    HttpResponse,
+    http::StatusCode,
+    post,
+    web::{Data, Json},
+};
+use serde_json::json;
+
+use crate::{
+    app_state::{AppState, Result},
+    models::requests::{
+        login_request_model::LoginRequest, refresh_token_request_model::RefreshTokenRequest,
+        register_request_model::RegisterRequest,
+    },
+    services::auth_service::{login, logout, refresh, register},
+};
+
+#[post("/register")]
+pub async fn register_handler(
+    app_state: Data<AppState>,
+    body: Json<RegisterRequest>,
+) -> Result<HttpResponse> {
+    let user = register(&app_state.database_connection, body.into_inner()).await?;
+
+    Ok(HttpResponse::Created().json(json!({
+        "code": StatusCode::CREATED.as_u16(),
+        "data": user
+    })))
+}
+
+#[post("/login")]
+pub async fn login_handler(
+    app_state: Data<AppState>,
+    body: Json<LoginRequest>,
+) -> Result<HttpResponse> {
+    let session = login(
+        &app_state.database_connection,
+        &app_state.config,
+        body.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": session
+    })))
+}
+
+#[post("/refresh")]
+pub async fn refresh_handler(
+    app_state: Data<AppState>,
+    body: Json<RefreshTokenRequest>,
+) -> Result<HttpResponse> {
+    let tokens = refresh(
+        &app_state.database_connection,
+        &app_state.config,
+        body.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": tokens
+    })))
+}
+
+#[post("/logout")]
+pub async fn logout_handler(
+    app_state: Data<AppState>,
+    body: Json<RefreshTokenRequest>,
+) -> Result<HttpResponse> {
+    logout(&app_state.database_connection, body.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "status": "OK"
+    })))
+}
@@ -0,0 +1,14 @@
+mod auth_routes;
+
+use actix_web::web::{ServiceConfig, scope};
+use auth_routes::{login_handler, logout_handler, refresh_handler, register_handler};
+
+pub fn auth_routes(config: &mut ServiceConfig) {
+    config.service(
+        scope("/auth")
+            .service(register_handler)
+            .service(login_handler)
+            .service(refresh_handler)
+            .service(logout_handler),
+    );
+}
@@ -0,0 +1,79 @@
+use actix_web::{
+    HttpResponse, get,
+    http::StatusCode,
+    post,
+    web::{Data, Json, Path},
+};
+use serde_json::json;
+
+use crate::{
+    app_state::{AppState, Result},
+    extractors::authenticated_user::AuthenticatedUser,
+    models::requests::cancel_booking_request_model::CancelBookingRequest,
+    services::{
+        booking_service::{cancel_booking, get_booking},
+        ticket_service::get_booking_tickets,
+    },
+};
+
+#[get("/{booking_id}")]
+pub async fn get_booking_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    booking_id: Path<String>,
+) -> Result<HttpResponse> {
+    let booking = get_booking(
+        &app_state.database_connection,
+        user.id,
+        booking_id.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": booking
+    })))
+}
+
+#[post("/{booking_id}/cancel")]
+pub async fn cancel_booking_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    booking_id: Path<String>,
+    body: Json<CancelBookingRequest>,
+) -> Result<HttpResponse> {
+    let booking = cancel_booking(
+        &app_state.database_connection,
+        &app_state.payment_gateway,
+        app_state.config.booking_cancellation_cutoff_minutes,
+        user.id,
+        booking_id.into_inner(),
+        body.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": booking
+    })))
+}
+
+#[get("/{booking_id}/tickets")]
+pub async fn get_booking_tickets_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    booking_id: Path<String>,
+) -> Result<HttpResponse> {
+    let tickets = get_booking_tickets(
+        &app_state.database_connection,
+        &app_state.config.ticket_signing_secret,
+        user.id,
+        booking_id.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": tickets
+    })))
+}
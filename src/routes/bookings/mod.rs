@@ -0,0 +1,13 @@
+mod bookings_routes;
+
+use actix_web::web::{ServiceConfig, scope};
+use bookings_routes::{cancel_booking_handler, get_booking_handler, get_booking_tickets_handler};
+
+pub fn bookings_routes(config: &mut ServiceConfig) {
+    config.service(
+        scope("/bookings")
+            .service(get_booking_handler)
+            .service(cancel_booking_handler)
+            .service(get_booking_tickets_handler),
+    );
+}
@@ -0,0 +1,45 @@
+use actix_web::{
+    HttpResponse,
+    http::StatusCode,
+    post,
+    web::{Data, Json},
+};
+use entity::sea_orm_active_enums::UserRole;
+use serde_json::json;
+
+use crate::{
+    app_state::{AppState, Result},
+    extractors::authenticated_user::AuthenticatedUser,
+    models::requests::check_in_request_model::CheckInRequest,
+    services::ticket_service::check_in_ticket,
+};
+
+#[post("")]
+pub async fn check_in_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    body: Json<CheckInRequest>,
+) -> Result<HttpResponse> {
+    user.require_role(&[
+        UserRole::Admin,
+        UserRole::TheaterManager,
+        UserRole::BoxOffice,
+    ])?;
+
+    let check_in = check_in_ticket(
+        &app_state.database_connection,
+        &app_state.config.ticket_signing_secret,
+        (
+            app_state.config.check_in_opens_minutes,
+            app_state.config.check_in_closes_minutes,
+        ),
+        &user,
+        body.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": check_in
+    })))
+}
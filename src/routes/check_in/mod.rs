@@ -0,0 +1,8 @@
+mod check_in_routes;
+
+use actix_web::web::{ServiceConfig, scope};
+use check_in_routes::check_in_handler;
+
+pub fn check_in_routes(config: &mut ServiceConfig) {
+    config.service(scope("/check-in").service(check_in_handler));
+}
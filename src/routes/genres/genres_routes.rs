@@ -0,0 +1,17 @@
+use actix_web::{HttpResponse, get, http::StatusCode, web::Data};
+use serde_json::json;
+
+use crate::{
+    app_state::{AppState, Result},
+    services::genres_service::get_genres,
+};
+
+#[get("")]
+pub async fn get_genres_handler(app_state: Data<AppState>) -> Result<HttpResponse> {
+    let genres = get_genres(&app_state.database_connection).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": genres
+    })))
+}
@@ -0,0 +1,8 @@
+mod genres_routes;
+
+use actix_web::web::{ServiceConfig, scope};
+use genres_routes::get_genres_handler;
+
+pub fn genres_routes(config: &mut ServiceConfig) {
+    config.service(scope("/genres").service(get_genres_handler));
+}
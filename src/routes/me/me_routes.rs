@@ -0,0 +1,46 @@
+use actix_web::{
+    HttpResponse, get,
+    http::StatusCode,
+    web::{Data, Query},
+};
+use serde_json::json;
+
+use crate::{
+    app_state::{AppState, Result},
+    extractors::authenticated_user::AuthenticatedUser,
+    models::requests::get_my_bookings_request_model::GetMyBookingsQueryParams,
+    services::{auth_service::get_user, booking_service::get_my_bookings},
+};
+
+#[get("")]
+pub async fn get_me_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+) -> Result<HttpResponse> {
+    let user = get_user(&app_state.database_connection, user.id).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": user
+    })))
+}
+
+#[get("/bookings")]
+pub async fn get_my_bookings_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    query_params: Query<GetMyBookingsQueryParams>,
+) -> Result<HttpResponse> {
+    let (bookings, info) = get_my_bookings(
+        &app_state.database_connection,
+        user.id,
+        query_params.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "OK",
+        "data": bookings,
+        "info": info
+    })))
+}
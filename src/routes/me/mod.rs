@@ -0,0 +1,12 @@
+mod me_routes;
+
+use actix_web::web::{ServiceConfig, scope};
+use me_routes::{get_me_handler, get_my_bookings_handler};
+
+pub fn me_routes(config: &mut ServiceConfig) {
+    config.service(
+        scope("/me")
+            .service(get_me_handler)
+            .service(get_my_bookings_handler),
+    );
+}
@@ -1,10 +1,26 @@
+mod auth;
+mod bookings;
+mod check_in;
+mod genres;
+mod me;
 mod movies;
 mod showtime;
 mod theaters;
+mod tickets;
+mod users;
 mod v1;
+mod webhooks;
 
-use actix_web::web::{ServiceConfig, scope};
+use crate::middlewares::authentication::authentication;
+use actix_web::{
+    middleware::from_fn,
+    web::{ServiceConfig, scope},
+};
 
 pub fn routes(config: &mut ServiceConfig) {
-    config.service(scope("/v1").configure(v1::v1_routes));
+    config.service(
+        scope("/v1")
+            .wrap(from_fn(authentication))
+            .configure(v1::v1_routes),
+    );
 }
@@ -1,11 +1,20 @@
 mod movies_routes;
 use actix_web::web::{ServiceConfig, scope};
-use movies_routes::{get_movie_handler, get_movies_handler};
+use movies_routes::{
+    create_movie_handler, delete_movie_handler, get_movie_handler, get_movies_handler,
+    replace_movie_handler, search_movies_handler, update_movie_handler,
+};
 
 pub fn movie_routes(config: &mut ServiceConfig) {
     config.service(
         scope("/movies")
             .service(get_movies_handler)
-            .service(get_movie_handler),
+            // Registered before `/{movie_id}` so that `search` is not taken for an id
+            .service(search_movies_handler)
+            .service(get_movie_handler)
+            .service(create_movie_handler)
+            .service(replace_movie_handler)
+            .service(update_movie_handler)
+            .service(delete_movie_handler),
     );
 }
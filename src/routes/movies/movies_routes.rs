@@ -1,14 +1,25 @@
 use actix_web::{
-    HttpResponse, get,
+    HttpResponse, delete, get,
     http::StatusCode,
-    web::{Data, Path, Query},
+    patch, post, put,
+    web::{Data, Json, Path, Query},
 };
+use entity::sea_orm_active_enums::UserRole;
 use serde_json::json;
 
 use crate::{
     app_state::{AppState, Result},
-    models::requests::get_movies_request_model::GetMoviesQueryParams,
-    services::movies_service::{get_movie, get_movies},
+    extractors::authenticated_user::AuthenticatedUser,
+    models::requests::{
+        create_movie_request_model::CreateMovieRequest,
+        get_movies_request_model::GetMoviesQueryParams,
+        search_movies_request_model::SearchMoviesQueryParams,
+        update_movie_request_model::UpdateMovieRequest,
+    },
+    services::movies_service::{
+        create_movie, delete_movie, get_movie, get_movies, replace_movie, search_movies,
+        update_movie,
+    },
 };
 
 #[get("")]
@@ -26,6 +37,19 @@ pub async fn get_movies_handler(
     })))
 }
 
+#[get("/search")]
+pub async fn search_movies_handler(
+    app_state: Data<AppState>,
+    query_params: Query<SearchMoviesQueryParams>,
+) -> Result<HttpResponse> {
+    let movies = search_movies(&app_state.database_connection, query_params.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": movies
+    })))
+}
+
 #[get("/{movie_id}")]
 pub async fn get_movie_handler(
     app_state: Data<AppState>,
@@ -38,3 +62,79 @@ pub async fn get_movie_handler(
         "data": movie
     })))
 }
+
+#[post("")]
+pub async fn create_movie_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    body: Json<CreateMovieRequest>,
+) -> Result<HttpResponse> {
+    user.require_role(&[UserRole::Admin])?;
+
+    let movie = create_movie(&app_state.database_connection, body.into_inner()).await?;
+
+    Ok(HttpResponse::Created().json(json!({
+        "code": StatusCode::CREATED.as_u16(),
+        "data": movie
+    })))
+}
+
+#[put("/{movie_id}")]
+pub async fn replace_movie_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    movie_id: Path<String>,
+    body: Json<CreateMovieRequest>,
+) -> Result<HttpResponse> {
+    user.require_role(&[UserRole::Admin])?;
+
+    let movie = replace_movie(
+        &app_state.database_connection,
+        movie_id.into_inner(),
+        body.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": movie
+    })))
+}
+
+#[patch("/{movie_id}")]
+pub async fn update_movie_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    movie_id: Path<String>,
+    body: Json<UpdateMovieRequest>,
+) -> Result<HttpResponse> {
+    user.require_role(&[UserRole::Admin])?;
+
+    let movie = update_movie(
+        &app_state.database_connection,
+        movie_id.into_inner(),
+        body.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": movie
+    })))
+}
+
+#[delete("/{movie_id}")]
+pub async fn delete_movie_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    movie_id: Path<String>,
+) -> Result<HttpResponse> {
+    user.require_role(&[UserRole::Admin])?;
+
+    delete_movie(&app_state.database_connection, movie_id.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "status": "OK"
+    })))
+}
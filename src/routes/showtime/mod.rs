@@ -2,12 +2,22 @@ mod showtime_routes;
 
 use crate::routes::showtime::showtime_routes::get_showtime_handler;
 use actix_web::web::{ServiceConfig, scope};
-use showtime_routes::get_taken_seats_handler;
+use showtime_routes::{
+    add_showtime_slots_handler, create_booking_handler, create_seat_hold_handler,
+    create_showtime_handler, get_seat_map_handler, get_taken_seats_handler,
+    release_seat_hold_handler,
+};
 
 pub fn showtime_routes(config: &mut ServiceConfig) {
     config.service(
         scope("/showtime")
             .service(get_showtime_handler)
-            .service(get_taken_seats_handler),
+            .service(create_showtime_handler)
+            .service(add_showtime_slots_handler)
+            .service(get_taken_seats_handler)
+            .service(get_seat_map_handler)
+            .service(create_booking_handler)
+            .service(create_seat_hold_handler)
+            .service(release_seat_hold_handler),
     );
 }
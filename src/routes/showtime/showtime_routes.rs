@@ -1,23 +1,87 @@
 use crate::app_state::{AppState, Result};
-use crate::services::showtime_service::{get_showtime, get_taken_seats};
+use crate::extractors::authenticated_user::AuthenticatedUser;
+use crate::models::requests::create_booking_request_model::CreateBookingRequest;
+use crate::models::requests::create_seat_hold_request_model::CreateSeatHoldRequest;
+use crate::models::requests::create_showtime_request_model::{
+    AddShowtimeSlotsRequest, CreateShowtimeRequest,
+};
+use crate::models::requests::get_showtime_request_model::GetShowtimeQueryParams;
+use crate::services::booking_service::create_booking;
+use crate::services::seat_hold_service::{create_seat_hold, release_seat_hold};
+use crate::services::seat_map_service::get_seat_map;
+use crate::services::showtime_service::{
+    add_showtime_slots, create_showtime, get_showtime, get_taken_seats,
+};
 use actix_web::http::StatusCode;
-use actix_web::web::Path;
-use actix_web::{HttpResponse, get, web::Data};
+use actix_web::web::{Json, Path, Query};
+use actix_web::{HttpResponse, delete, get, post, web::Data};
+use entity::sea_orm_active_enums::UserRole;
 use serde::Deserialize;
 use serde_json::json;
 
 #[get("")]
-pub async fn get_showtime_handler(state: Data<AppState>) -> Result<HttpResponse> {
-    let showtime = get_showtime(&state.database_connection).await?;
+pub async fn get_showtime_handler(
+    state: Data<AppState>,
+    query_params: Query<GetShowtimeQueryParams>,
+) -> Result<HttpResponse> {
+    let (showtime, info) =
+        get_showtime(&state.database_connection, query_params.into_inner()).await?;
 
     Ok(HttpResponse::Ok().json(json!({
         "status": "OK",
+        "data": showtime,
+        "info": info
+    })))
+}
+
+#[post("")]
+pub async fn create_showtime_handler(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    body: Json<CreateShowtimeRequest>,
+) -> Result<HttpResponse> {
+    user.require_role(&[UserRole::Admin, UserRole::TheaterManager])?;
+
+    let showtime = create_showtime(
+        &state.database_connection,
+        &user,
+        state.config.showtime_cleaning_buffer_minutes,
+        body.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(json!({
+        "code": StatusCode::CREATED.as_u16(),
+        "data": showtime
+    })))
+}
+
+#[post("/{showtime_id}/showtime-rooms")]
+pub async fn add_showtime_slots_handler(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    showtime_id: Path<String>,
+    body: Json<AddShowtimeSlotsRequest>,
+) -> Result<HttpResponse> {
+    user.require_role(&[UserRole::Admin, UserRole::TheaterManager])?;
+
+    let showtime = add_showtime_slots(
+        &state.database_connection,
+        &user,
+        state.config.showtime_cleaning_buffer_minutes,
+        showtime_id.into_inner(),
+        body.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(json!({
+        "code": StatusCode::CREATED.as_u16(),
         "data": showtime
     })))
 }
 
 #[derive(Deserialize)]
-struct GetTakenSeatsPath {
+struct ShowtimeRoomPath {
     showtime_id: String,
     showtime_room_id: i32,
 }
@@ -25,7 +89,7 @@ struct GetTakenSeatsPath {
 #[get("/{showtime_id}/showtime-rooms/{showtime_room_id}/taken-seats")]
 pub async fn get_taken_seats_handler(
     state: Data<AppState>,
-    path: Path<GetTakenSeatsPath>,
+    path: Path<ShowtimeRoomPath>,
 ) -> Result<HttpResponse> {
     let path = path.into_inner();
 
@@ -41,3 +105,104 @@ pub async fn get_taken_seats_handler(
         "data": taken_seats
     })))
 }
+
+#[get("/{showtime_id}/showtime-rooms/{showtime_room_id}/seat-map")]
+pub async fn get_seat_map_handler(
+    state: Data<AppState>,
+    path: Path<ShowtimeRoomPath>,
+) -> Result<HttpResponse> {
+    let path = path.into_inner();
+
+    let seat_map = get_seat_map(
+        &state.database_connection,
+        path.showtime_id,
+        path.showtime_room_id,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": seat_map
+    })))
+}
+
+#[post("/{showtime_id}/showtime-rooms/{showtime_room_id}/bookings")]
+pub async fn create_booking_handler(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    path: Path<ShowtimeRoomPath>,
+    body: Json<CreateBookingRequest>,
+) -> Result<HttpResponse> {
+    let path = path.into_inner();
+
+    let booking = create_booking(
+        &state.database_connection,
+        &state.payment_gateway,
+        user.id,
+        path.showtime_id,
+        path.showtime_room_id,
+        body.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(json!({
+        "code": StatusCode::CREATED.as_u16(),
+        "data": booking
+    })))
+}
+
+#[post("/{showtime_id}/showtime-rooms/{showtime_room_id}/holds")]
+pub async fn create_seat_hold_handler(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    path: Path<ShowtimeRoomPath>,
+    body: Json<CreateSeatHoldRequest>,
+) -> Result<HttpResponse> {
+    let path = path.into_inner();
+
+    let seat_hold = create_seat_hold(
+        &state.database_connection,
+        state.config.seat_hold_ttl_seconds,
+        state.config.seat_hold_max_seats_per_user,
+        user.id,
+        path.showtime_id,
+        path.showtime_room_id,
+        body.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(json!({
+        "code": StatusCode::CREATED.as_u16(),
+        "data": seat_hold
+    })))
+}
+
+#[derive(Deserialize)]
+struct SeatHoldPath {
+    showtime_id: String,
+    showtime_room_id: i32,
+    hold_id: String,
+}
+
+#[delete("/{showtime_id}/showtime-rooms/{showtime_room_id}/holds/{hold_id}")]
+pub async fn release_seat_hold_handler(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    path: Path<SeatHoldPath>,
+) -> Result<HttpResponse> {
+    let path = path.into_inner();
+
+    release_seat_hold(
+        &state.database_connection,
+        user.id,
+        path.showtime_id,
+        path.showtime_room_id,
+        path.hold_id,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "status": "OK"
+    })))
+}
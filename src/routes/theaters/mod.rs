@@ -1,12 +1,28 @@
+mod rooms_routes;
 mod theaters_routes;
 
 use actix_web::web::{ServiceConfig, scope};
-use theaters_routes::{get_theater_showtime_handler, get_theaters_handler};
+use rooms_routes::{
+    create_room_handler, delete_room_handler, get_room_handler, get_rooms_handler,
+    update_room_handler,
+};
+use theaters_routes::{
+    create_theater_handler, delete_theater_handler, get_theater_showtime_handler,
+    get_theaters_handler, update_theater_handler,
+};
 
 pub fn theaters_routes(config: &mut ServiceConfig) {
     config.service(
         scope("/theaters")
             .service(get_theaters_handler)
-            .service(get_theater_showtime_handler),
+            .service(get_theater_showtime_handler)
+            .service(create_theater_handler)
+            .service(update_theater_handler)
+            .service(delete_theater_handler)
+            .service(get_rooms_handler)
+            .service(get_room_handler)
+            .service(create_room_handler)
+            .service(update_room_handler)
+            .service(delete_room_handler),
     );
 }
@@ -0,0 +1,120 @@
+use actix_web::{
+    HttpResponse, delete, get,
+    http::StatusCode,
+    patch, post,
+    web::{Data, Json, Path},
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    app_state::{AppState, Result},
+    extractors::authenticated_user::AuthenticatedUser,
+    models::requests::{
+        create_room_request_model::CreateRoomRequest, update_room_request_model::UpdateRoomRequest,
+    },
+    services::rooms_service::{create_room, delete_room, get_room, get_rooms, update_room},
+};
+
+#[derive(Deserialize)]
+struct RoomPath {
+    theater_id: String,
+    room_id: String,
+}
+
+#[get("/{theater_id}/rooms")]
+pub async fn get_rooms_handler(
+    app_state: Data<AppState>,
+    theater_id: Path<String>,
+) -> Result<HttpResponse> {
+    let rooms = get_rooms(&app_state.database_connection, theater_id.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": rooms
+    })))
+}
+
+#[get("/{theater_id}/rooms/{room_id}")]
+pub async fn get_room_handler(
+    app_state: Data<AppState>,
+    path: Path<RoomPath>,
+) -> Result<HttpResponse> {
+    let path = path.into_inner();
+    let room = get_room(
+        &app_state.database_connection,
+        path.theater_id,
+        path.room_id,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": room
+    })))
+}
+
+#[post("/{theater_id}/rooms")]
+pub async fn create_room_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    theater_id: Path<String>,
+    body: Json<CreateRoomRequest>,
+) -> Result<HttpResponse> {
+    let room = create_room(
+        &app_state.database_connection,
+        &user,
+        theater_id.into_inner(),
+        body.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(json!({
+        "code": StatusCode::CREATED.as_u16(),
+        "data": room
+    })))
+}
+
+#[patch("/{theater_id}/rooms/{room_id}")]
+pub async fn update_room_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    path: Path<RoomPath>,
+    body: Json<UpdateRoomRequest>,
+) -> Result<HttpResponse> {
+    let path = path.into_inner();
+    let room = update_room(
+        &app_state.database_connection,
+        &user,
+        path.theater_id,
+        path.room_id,
+        body.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": room
+    })))
+}
+
+#[delete("/{theater_id}/rooms/{room_id}")]
+pub async fn delete_room_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    path: Path<RoomPath>,
+) -> Result<HttpResponse> {
+    let path = path.into_inner();
+    delete_room(
+        &app_state.database_connection,
+        &user,
+        path.theater_id,
+        path.room_id,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "status": "OK"
+    })))
+}
@@ -1,17 +1,30 @@
 use crate::{
     app_state::{AppState, Result},
-    services::theaters_service::{get_theater_showtime, get_theaters},
+    extractors::authenticated_user::AuthenticatedUser,
+    models::requests::{
+        create_theater_request_model::CreateTheaterRequest,
+        get_theaters_request_model::GetTheatersQueryParams,
+        update_theater_request_model::UpdateTheaterRequest,
+    },
+    services::theaters_service::{
+        create_theater, delete_theater, get_theater_showtime, get_theaters, update_theater,
+    },
 };
 use actix_web::{
-    HttpResponse, get,
+    HttpResponse, delete, get,
     http::StatusCode,
-    web::{Data, Path},
+    patch, post,
+    web::{Data, Json, Path, Query},
 };
+use entity::sea_orm_active_enums::UserRole;
 use serde_json::json;
 
 #[get("")]
-pub async fn get_theaters_handler(app_state: Data<AppState>) -> Result<HttpResponse> {
-    let theaters = get_theaters(&app_state.database_connection).await?;
+pub async fn get_theaters_handler(
+    app_state: Data<AppState>,
+    query: Query<GetTheatersQueryParams>,
+) -> Result<HttpResponse> {
+    let theaters = get_theaters(&app_state.database_connection, query.into_inner()).await?;
 
     Ok(HttpResponse::Ok().json(json!({
         "status": "OK",
@@ -32,3 +45,56 @@ pub async fn get_theater_showtime_handler(
         "data": showtime
     })))
 }
+
+#[post("")]
+pub async fn create_theater_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    body: Json<CreateTheaterRequest>,
+) -> Result<HttpResponse> {
+    user.require_role(&[UserRole::Admin])?;
+
+    let theater = create_theater(&app_state.database_connection, body.into_inner()).await?;
+
+    Ok(HttpResponse::Created().json(json!({
+        "code": StatusCode::CREATED.as_u16(),
+        "data": theater
+    })))
+}
+
+#[patch("/{theater_id}")]
+pub async fn update_theater_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    theater_id: Path<String>,
+    body: Json<UpdateTheaterRequest>,
+) -> Result<HttpResponse> {
+    let theater = update_theater(
+        &app_state.database_connection,
+        &user,
+        theater_id.into_inner(),
+        body.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": theater
+    })))
+}
+
+#[delete("/{theater_id}")]
+pub async fn delete_theater_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    theater_id: Path<String>,
+) -> Result<HttpResponse> {
+    user.require_role(&[UserRole::Admin])?;
+
+    delete_theater(&app_state.database_connection, theater_id.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "status": "OK"
+    })))
+}
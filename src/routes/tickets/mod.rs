@@ -0,0 +1,8 @@
+mod tickets_routes;
+
+use actix_web::web::{ServiceConfig, scope};
+use tickets_routes::get_ticket_qr_handler;
+
+pub fn tickets_routes(config: &mut ServiceConfig) {
+    config.service(scope("/tickets").service(get_ticket_qr_handler));
+}
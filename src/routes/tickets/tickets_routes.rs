@@ -0,0 +1,30 @@
+use actix_web::{
+    HttpResponse, get,
+    web::{Data, Path, Query},
+};
+
+use crate::{
+    app_state::{AppState, Result},
+    extractors::authenticated_user::AuthenticatedUser,
+    models::requests::get_ticket_qr_request_model::GetTicketQrQueryParams,
+    services::ticket_service::render_ticket_qr,
+};
+
+#[get("/{ticket_id}/qr")]
+pub async fn get_ticket_qr_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    ticket_id: Path<String>,
+    query_params: Query<GetTicketQrQueryParams>,
+) -> Result<HttpResponse> {
+    let (content_type, image) = render_ticket_qr(
+        &app_state.database_connection,
+        &app_state.config.ticket_signing_secret,
+        user.id,
+        ticket_id.into_inner(),
+        query_params.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().content_type(content_type).body(image))
+}
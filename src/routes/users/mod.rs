@@ -0,0 +1,8 @@
+mod users_routes;
+
+use actix_web::web::{ServiceConfig, scope};
+use users_routes::update_user_role_handler;
+
+pub fn users_routes(config: &mut ServiceConfig) {
+    config.service(scope("/users").service(update_user_role_handler));
+}
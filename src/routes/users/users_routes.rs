@@ -0,0 +1,38 @@
+use actix_web::{
+    HttpResponse,
+    http::StatusCode,
+    put,
+    web::{Data, Json, Path},
+};
+use entity::sea_orm_active_enums::UserRole;
+use serde_json::json;
+
+use crate::{
+    app_state::{AppState, Result},
+    extractors::authenticated_user::AuthenticatedUser,
+    models::requests::update_user_role_request_model::UpdateUserRoleRequest,
+    services::auth_service::update_user_role,
+};
+
+#[put("/{user_id}/role")]
+pub async fn update_user_role_handler(
+    app_state: Data<AppState>,
+    user: AuthenticatedUser,
+    user_id: Path<String>,
+    body: Json<UpdateUserRoleRequest>,
+) -> Result<HttpResponse> {
+    user.require_role(&[UserRole::Admin, UserRole::TheaterManager])?;
+
+    let updated_user = update_user_role(
+        &app_state.database_connection,
+        &user,
+        user_id.into_inner(),
+        body.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": updated_user
+    })))
+}
@@ -1,5 +1,13 @@
+use crate::routes::auth::auth_routes;
+use crate::routes::bookings::bookings_routes;
+use crate::routes::check_in::check_in_routes;
+use crate::routes::genres::genres_routes;
+use crate::routes::me::me_routes;
 use crate::routes::showtime::showtime_routes;
 use crate::routes::theaters::theaters_routes;
+use crate::routes::tickets::tickets_routes;
+use crate::routes::users::users_routes;
+use crate::routes::webhooks::webhooks_routes;
 use actix_web::web::ServiceConfig;
 
 use super::movies::movie_routes;
@@ -8,5 +16,13 @@ pub fn v1_routes(config: &mut ServiceConfig) {
     config
         .configure(theaters_routes)
         .configure(showtime_routes)
-        .configure(movie_routes);
+        .configure(movie_routes)
+        .configure(genres_routes)
+        .configure(bookings_routes)
+        .configure(tickets_routes)
+        .configure(check_in_routes)
+        .configure(auth_routes)
+        .configure(me_routes)
+        .configure(users_routes)
+        .configure(webhooks_routes);
 }
@@ -0,0 +1,8 @@
+mod webhooks_routes;
+
+use actix_web::web::{ServiceConfig, scope};
+use webhooks_routes::payment_webhook_handler;
+
+pub fn webhooks_routes(config: &mut ServiceConfig) {
+    config.service(scope("/webhooks").service(payment_webhook_handler));
+}
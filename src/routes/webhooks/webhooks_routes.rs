@@ -0,0 +1,43 @@
+use actix_web::{
+    HttpRequest, HttpResponse,
+    http::StatusCode,
+    post,
+    web::{Bytes, Data},
+};
+use serde_json::json;
+
+use crate::{
+    app_state::{AppState, Result},
+    services::payment_webhook_service::handle_payment_webhook,
+};
+
+/// Header carrying the hex encoded HMAC-SHA256 of the request body.
+const SIGNATURE_HEADER: &str = "X-Payment-Signature";
+
+#[post("/payments")]
+pub async fn payment_webhook_handler(
+    app_state: Data<AppState>,
+    req: HttpRequest,
+    body: Bytes,
+) -> Result<HttpResponse> {
+    let signature = req
+        .headers()
+        .get(SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok());
+
+    let is_new_event = handle_payment_webhook(
+        &app_state.database_connection,
+        &app_state.payment_gateway,
+        app_state.config.payment_webhook_secret.as_deref(),
+        signature,
+        &body,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "code": StatusCode::OK.as_u16(),
+        "data": {
+            "duplicate": !is_new_event
+        }
+    })))
+}
@@ -0,0 +1,488 @@
+use anyhow::anyhow;
+use argon2::{
+    Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
+    password_hash::{
+        SaltString,
+        rand_core::{OsRng, RngCore},
+    },
+};
+use chrono::{Duration, NaiveDateTime, Utc};
+use entity::{refresh_token, sea_orm_active_enums::UserRole, theater, user};
+use jsonwebtoken::{
+    DecodingKey, EncodingKey, Header, Validation, decode, encode, errors::ErrorKind,
+};
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, ConnectionTrait,
+    DatabaseConnection, EntityTrait, IntoActiveModel, Iterable, QueryFilter, QuerySelect, SqlErr,
+    TransactionTrait, sea_query::Expr,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::{
+    app_error::{AppError, FieldError},
+    app_state::Result,
+    config::Config,
+    extractors::authenticated_user::AuthenticatedUser,
+    models::{
+        requests::{
+            login_request_model::LoginRequest, refresh_token_request_model::RefreshTokenRequest,
+            register_request_model::RegisterRequest,
+            update_user_role_request_model::UpdateUserRoleRequest,
+        },
+        user_model::{AuthTokens, Session, User},
+    },
+};
+
+const MIN_PASSWORD_LEN: usize = 8;
+
+/// Hash of a random password that was thrown away, checked against when logging in with an
+/// unknown email so that it takes as long as logging in with a known one. Uses the default
+/// Argon2 parameters, like the hashes of real passwords.
+const DUMMY_PASSWORD_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$QuWTDDY48EpfvH2ZEbMOoQ$INpmQLyPBynsqttYqTh54rFoLJ76rl1U/Inkgu7gKq8";
+
+fn map_user(user: user::Model) -> User {
+    User {
+        id: user.id.to_string(),
+        email: user.email,
+        name: user.name,
+        role: user.role.to_value(),
+        theater_id: user.theater_id.map(|theater_id| theater_id.to_string()),
+        created_at: user.created_at,
+    }
+}
+
+fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+fn validate_register_request(request: &RegisterRequest) -> Result<()> {
+    let mut errors = vec![];
+
+    let email = normalize_email(&request.email);
+    let is_valid_email = email
+        .split_once('@')
+        .is_some_and(|(local, domain)| !local.is_empty() && domain.contains('.'));
+    if !is_valid_email {
+        errors.push(FieldError {
+            field: "email".to_string(),
+            message: "Email is not a valid email address".to_string(),
+        });
+    }
+
+    if request.name.trim().is_empty() {
+        errors.push(FieldError {
+            field: "name".to_string(),
+            message: "Name must not be empty".to_string(),
+        });
+    }
+
+    if request.password.chars().count() < MIN_PASSWORD_LEN {
+        errors.push(FieldError {
+            field: "password".to_string(),
+            message: format!("Password must be at least {MIN_PASSWORD_LEN} characters long"),
+        });
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Validation(errors))
+    }
+}
+
+// Argon2 is deliberately slow, so it runs on the blocking thread pool instead of the worker.
+async fn hash_password(password: String) -> Result<String> {
+    let password_hash = actix_web::rt::task::spawn_blocking(move || {
+        let salt = SaltString::generate(&mut OsRng);
+
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| anyhow!("Failed to hash password: {e}"))
+    })
+    .await
+    .map_err(|e| anyhow!("Password hashing task failed: {e}"))??;
+
+    Ok(password_hash)
+}
+
+async fn verify_password(password: String, password_hash: String) -> Result<bool> {
+    let is_valid = actix_web::rt::task::spawn_blocking(move || {
+        let parsed_hash = PasswordHash::new(&password_hash)
+            .map_err(|e| anyhow!("Failed to parse password hash: {e}"))?;
+
+        Ok::<_, anyhow::Error>(
+            Argon2::default()
+                .verify_password(password.as_bytes(), &parsed_hash)
+                .is_ok(),
+        )
+    })
+    .await
+    .map_err(|e| anyhow!("Password verification task failed: {e}"))??;
+
+    Ok(is_valid)
+}
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+// Only a digest of the token is stored, so a leaked database does not leak usable tokens.
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+// The role is carried in the access token so that guards do not need a database round trip.
+// A role change therefore takes effect once the user's access token is refreshed.
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    sub: String,
+    role: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    theater_id: Option<String>,
+    iat: i64,
+    exp: i64,
+}
+
+fn issue_access_token(config: &Config, user: &user::Model) -> Result<(String, NaiveDateTime)> {
+    let now = Utc::now();
+    let expires_at = now + Duration::minutes(config.access_token_ttl_minutes);
+
+    let claims = Claims {
+        sub: user.id.to_string(),
+        role: user.role.to_value(),
+        theater_id: user.theater_id.map(|theater_id| theater_id.to_string()),
+        iat: now.timestamp(),
+        exp: expires_at.timestamp(),
+    };
+
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(config.jwt_secret.as_bytes()),
+    )
+    .map_err(|e| anyhow!("Failed to sign access token: {e}"))?;
+
+    Ok((token, expires_at.naive_utc()))
+}
+
+pub fn decode_access_token(config: &Config, token: &str) -> Result<AuthenticatedUser> {
+    let token_data = decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(config.jwt_secret.as_bytes()),
+        &Validation::default(),
+    )
+    .map_err(|e| match e.kind() {
+        ErrorKind::ExpiredSignature => {
+            AppError::Unauthorized("Access token has expired".to_string())
+        }
+        _ => AppError::Unauthorized("Access token is invalid".to_string()),
+    })?;
+
+    let claims = token_data.claims;
+    let invalid_token = || AppError::Unauthorized("Access token is invalid".to_string());
+
+    Ok(AuthenticatedUser {
+        id: Uuid::from_str(&claims.sub).map_err(|_| invalid_token())?,
+        role: UserRole::try_from_value(&claims.role).map_err(|_| invalid_token())?,
+        theater_id: claims
+            .theater_id
+            .map(|theater_id| Uuid::from_str(&theater_id))
+            .transpose()
+            .map_err(|_| invalid_token())?,
+    })
+}
+
+async fn issue_tokens<C: ConnectionTrait>(
+    db: &C,
+    config: &Config,
+    user: &user::Model,
+    family_id: Uuid,
+) -> Result<(AuthTokens, refresh_token::Model)> {
+    let (access_token, access_token_expires_at) = issue_access_token(config, user)?;
+
+    let refresh_token = generate_token();
+    let now = Utc::now().naive_utc();
+
+    let refresh_token_model = refresh_token::ActiveModel {
+        id: Set(Uuid::now_v7()),
+        user_id: Set(user.id),
+        family_id: Set(family_id),
+        token_hash: Set(hash_token(&refresh_token)),
+        expires_at: Set(now + Duration::days(config.refresh_token_ttl_days)),
+        revoked_at: Set(None),
+        replaced_by: Set(None),
+        created_at: Set(now),
+    }
+    .insert(db)
+    .await?;
+
+    Ok((
+        AuthTokens {
+            access_token,
+            access_token_expires_at,
+            refresh_token,
+            refresh_token_expires_at: refresh_token_model.expires_at,
+        },
+        refresh_token_model,
+    ))
+}
+
+async fn revoke_refresh_token_family<C: ConnectionTrait>(db: &C, family_id: Uuid) -> Result<()> {
+    refresh_token::Entity::update_many()
+        .col_expr(
+            refresh_token::Column::RevokedAt,
+            Expr::value(Utc::now().naive_utc()),
+        )
+        .filter(
+            Condition::all()
+                .add(refresh_token::Column::FamilyId.eq(family_id))
+                .add(refresh_token::Column::RevokedAt.is_null()),
+        )
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn register(db: &DatabaseConnection, request: RegisterRequest) -> Result<User> {
+    validate_register_request(&request)?;
+
+    let email = normalize_email(&request.email);
+    let password_hash = hash_password(request.password).await?;
+    let now = Utc::now().naive_utc();
+
+    let user = user::ActiveModel {
+        id: Set(Uuid::now_v7()),
+        email: Set(email.to_owned()),
+        name: Set(request.name.trim().to_string()),
+        password_hash: Set(password_hash),
+        role: Set(UserRole::Customer),
+        theater_id: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(db)
+    .await
+    .map_err(|e| match e.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(_)) => {
+            AppError::Conflict(format!("An account with email: {} already exists", email))
+        }
+        _ => e.into(),
+    })?;
+
+    Ok(map_user(user))
+}
+
+pub async fn login(
+    db: &DatabaseConnection,
+    config: &Config,
+    request: LoginRequest,
+) -> Result<Session> {
+    let invalid_credentials = || AppError::Unauthorized("Invalid email or password".to_string());
+
+    let user = user::Entity::find()
+        .filter(user::Column::Email.eq(normalize_email(&request.email)))
+        .one(db)
+        .await?;
+
+    // The password is checked even when the email is unknown, so the response time does not
+    // tell which emails have an account
+    let password_hash = user
+        .as_ref()
+        .map_or(DUMMY_PASSWORD_HASH, |user| &user.password_hash);
+    let is_valid = verify_password(request.password, password_hash.to_string()).await?;
+
+    let user = user.filter(|_| is_valid).ok_or_else(invalid_credentials)?;
+
+    // Every login starts a new family of refresh tokens
+    let (tokens, _) = issue_tokens(db, config, &user, Uuid::now_v7()).await?;
+
+    Ok(Session {
+        tokens,
+        user: map_user(user),
+    })
+}
+
+/// Exchanges a refresh token for a new pair of tokens. Refresh tokens are single use, and
+/// presenting one that was already rotated revokes its whole family, since that means it
+/// has been stolen.
+pub async fn refresh(
+    db: &DatabaseConnection,
+    config: &Config,
+    request: RefreshTokenRequest,
+) -> Result<AuthTokens> {
+    let txn = db.begin().await?;
+
+    let current = refresh_token::Entity::find()
+        .filter(refresh_token::Column::TokenHash.eq(hash_token(&request.refresh_token)))
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::Unauthorized("Refresh token is invalid".to_string()))?;
+
+    if current.revoked_at.is_some() {
+        revoke_refresh_token_family(&txn, current.family_id).await?;
+        txn.commit().await?;
+
+        return Err(AppError::Unauthorized(
+            "Refresh token has been revoked".to_string(),
+        ));
+    }
+
+    if current.expires_at <= Utc::now().naive_utc() {
+        return Err(AppError::Unauthorized(
+            "Refresh token has expired".to_string(),
+        ));
+    }
+
+    let user = user::Entity::find_by_id(current.user_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| anyhow!("User with id: {} is missing", current.user_id))?;
+
+    let (tokens, next) = issue_tokens(&txn, config, &user, current.family_id).await?;
+
+    let mut current = current.into_active_model();
+    current.revoked_at = Set(Some(Utc::now().naive_utc()));
+    current.replaced_by = Set(Some(next.id));
+    current.update(&txn).await?;
+
+    txn.commit().await?;
+
+    Ok(tokens)
+}
+
+pub async fn logout(db: &DatabaseConnection, request: RefreshTokenRequest) -> Result<()> {
+    let current = refresh_token::Entity::find()
+        .filter(refresh_token::Column::TokenHash.eq(hash_token(&request.refresh_token)))
+        .one(db)
+        .await?;
+
+    if let Some(current) = current {
+        revoke_refresh_token_family(db, current.family_id).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn get_user(db: &DatabaseConnection, user_id: Uuid) -> Result<User> {
+    let user = user::Entity::find_by_id(user_id).one(db).await?;
+
+    match user {
+        Some(user) => Ok(map_user(user)),
+        None => Err(AppError::NotFound(format!(
+            "User with id: {} does not exist",
+            user_id
+        ))),
+    }
+}
+
+fn parse_role_request(request: UpdateUserRoleRequest) -> Result<(UserRole, Option<Uuid>)> {
+    let mut errors = vec![];
+
+    let role = UserRole::try_from_value(&request.role.trim().to_lowercase()).ok();
+    if role.is_none() {
+        errors.push(FieldError {
+            field: "role".to_string(),
+            message: format!(
+                "Role must be one of: {}",
+                UserRole::iter()
+                    .map(|role| role.to_value())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        });
+    }
+
+    let theater_id = request
+        .theater_id
+        .map(|theater_id| Uuid::from_str(&theater_id))
+        .transpose()?;
+
+    // Staff work at one theater, customers and admins are not tied to any
+    match (&role, theater_id) {
+        (Some(UserRole::BoxOffice | UserRole::TheaterManager), None) => {
+            errors.push(FieldError {
+                field: "theaterId".to_string(),
+                message: "Staff roles require a theater".to_string(),
+            });
+        }
+        (Some(UserRole::Customer | UserRole::Admin), Some(_)) => {
+            errors.push(FieldError {
+                field: "theaterId".to_string(),
+                message: "Only staff roles can be assigned to a theater".to_string(),
+            });
+        }
+        _ => {}
+    }
+
+    match role {
+        Some(role) if errors.is_empty() => Ok((role, theater_id)),
+        _ => Err(AppError::Validation(errors)),
+    }
+}
+
+/// Admins can assign any role. Theater managers can only hire and dismiss box-office staff
+/// of their own theater.
+pub async fn update_user_role(
+    db: &DatabaseConnection,
+    actor: &AuthenticatedUser,
+    user_id: String,
+    request: UpdateUserRoleRequest,
+) -> Result<User> {
+    let user_id = Uuid::from_str(&user_id)?;
+    let (role, theater_id) = parse_role_request(request)?;
+
+    let user = user::Entity::find_by_id(user_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("User with id: {} does not exist", user_id)))?;
+
+    if actor.role != UserRole::Admin {
+        let manages_user = match user.role {
+            UserRole::Customer => true,
+            UserRole::BoxOffice => user
+                .theater_id
+                .is_some_and(|theater_id| actor.require_theater_manager(theater_id).is_ok()),
+            UserRole::TheaterManager | UserRole::Admin => false,
+        };
+
+        if !manages_user || !matches!(role, UserRole::Customer | UserRole::BoxOffice) {
+            return Err(AppError::Forbidden(
+                "Theater managers can only manage box-office staff".to_string(),
+            ));
+        }
+
+        if let Some(theater_id) = theater_id {
+            actor.require_theater_manager(theater_id)?;
+        }
+    }
+
+    if let Some(theater_id) = theater_id
+        && theater::Entity::find_by_id(theater_id)
+            .one(db)
+            .await?
+            .is_none()
+    {
+        return Err(AppError::NotFound(format!(
+            "Theater with id: {} does not exist",
+            theater_id
+        )));
+    }
+
+    let mut user = user.into_active_model();
+    user.role = Set(role);
+    user.theater_id = Set(theater_id);
+    user.updated_at = Set(Utc::now().naive_utc());
+
+    let user = user.update(db).await?;
+
+    Ok(map_user(user))
+}
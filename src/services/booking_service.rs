@@ -0,0 +1,1076 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use entity::{
+    booking, movie, room,
+    sea_orm_active_enums::{BookingStatus, RefundStatus},
+    seat_hold, showtime, showtime_room, taken_seat, theater,
+};
+use sea_orm::{
+    ActiveEnum, ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, DatabaseConnection,
+    DatabaseTransaction, EntityTrait, FromQueryResult, IntoActiveModel, JoinType, Order,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, RelationTrait,
+    TransactionTrait, prelude::DateTimeWithTimeZone, sea_query::OnConflict,
+};
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::{
+    app_error::{AppError, FieldError},
+    app_state::Result,
+    models::{
+        booking_model::{BookedMovie, BookedTheater, Booking, BookingHistory},
+        requests::{
+            cancel_booking_request_model::CancelBookingRequest,
+            create_booking_request_model::CreateBookingRequest,
+            get_my_bookings_request_model::GetMyBookingsQueryParams,
+        },
+    },
+};
+
+use crate::payments::{CreatePaymentIntent, PaymentGateway};
+
+use super::{
+    pagination_service::{Info, validate_pagination},
+    seat_hold_service::find_held_seats,
+    seat_map_service::{find_room, validate_seats_in_room},
+    ticket_service::{issue_tickets, void_tickets},
+    timezone_service::to_local_time,
+};
+
+const MAX_SEATS_PER_BOOKING: usize = 10;
+
+fn map_booking(booking: booking::Model) -> Booking {
+    Booking {
+        id: booking.id.to_string(),
+        showtime_id: booking.showtime_id.to_string(),
+        showtime_room_id: booking.showtime_room_id,
+        seats: booking.seats,
+        amount: booking.amount,
+        status: booking.status.to_value(),
+        refund_status: booking.refund_status.to_value(),
+        cancellation_reason: booking.cancellation_reason,
+        cancelled_at: booking.cancelled_at,
+        created_at: booking.created_at,
+    }
+}
+
+// Seat identifiers are row letters followed by the column number, e.g. "A1" or "C12",
+// and have to fit in the 3 characters of `taken_seat.seat_identifier`.
+fn is_valid_seat_identifier(seat: &str) -> bool {
+    let row_len = seat.chars().take_while(char::is_ascii_uppercase).count();
+    let column = &seat[row_len..];
+
+    seat.len() <= 3
+        && row_len > 0
+        && !column.is_empty()
+        && column.chars().all(|c| c.is_ascii_digit())
+        && !column.starts_with('0')
+}
+
+fn validate_seats(seats: &[String]) -> Result<()> {
+    let mut errors = vec![];
+
+    if seats.is_empty() {
+        errors.push(FieldError {
+            field: "seats".to_string(),
+            message: "At least one seat must be booked".to_string(),
+        });
+    }
+
+    if seats.len() > MAX_SEATS_PER_BOOKING {
+        errors.push(FieldError {
+            field: "seats".to_string(),
+            message: format!("A booking can have at most {MAX_SEATS_PER_BOOKING} seats"),
+        });
+    }
+
+    for (index, seat) in seats.iter().enumerate() {
+        if !is_valid_seat_identifier(seat) {
+            errors.push(FieldError {
+                field: format!("seats[{index}]"),
+                message: format!("{seat} is not a valid seat identifier"),
+            });
+        } else if seats[..index].contains(seat) {
+            errors.push(FieldError {
+                field: format!("seats[{index}]"),
+                message: format!("{seat} is requested more than once"),
+            });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Validation(errors))
+    }
+}
+
+/// Normalises the requested seat identifiers and validates them.
+pub fn parse_seats(seats: &[String]) -> Result<Vec<String>> {
+    let seats = seats
+        .iter()
+        .map(|seat| seat.trim().to_uppercase())
+        .collect::<Vec<_>>();
+    validate_seats(&seats)?;
+
+    Ok(seats)
+}
+
+/// Locks the showtime room row for the rest of the transaction, so that bookings and holds
+/// for the same room are serialised.
+pub async fn lock_showtime_room(
+    txn: &DatabaseTransaction,
+    showtime_id: Uuid,
+    showtime_room_id: i32,
+) -> Result<showtime_room::Model> {
+    showtime_room::Entity::find_by_id(showtime_room_id)
+        .filter(showtime_room::Column::ShowtimeId.eq(showtime_id))
+        .lock_exclusive()
+        .one(txn)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!(
+                "Showtime room with id: {} does not exist for showtime: {}",
+                showtime_room_id, showtime_id
+            ))
+        })
+}
+
+/// Releases the seats sold to a booking so they can be booked again, voiding their tickets.
+pub async fn release_seats(txn: &DatabaseTransaction, booking_id: Uuid) -> Result<()> {
+    void_tickets(txn, booking_id).await?;
+
+    taken_seat::Entity::delete_many()
+        .filter(taken_seat::Column::BookingId.eq(booking_id))
+        .exec(txn)
+        .await?;
+
+    Ok(())
+}
+
+/// Fails a booking that is still waiting for its payment and releases the seats it was sold,
+/// returning whether it was failed. A booking whose payment was authorised is recorded as
+/// pending a refund. Bookings that moved on in the meantime, e.g. confirmed by a payment webhook,
+/// are left alone.
+async fn fail_booking(
+    db: &DatabaseConnection,
+    booking_id: Uuid,
+    is_authorised: bool,
+) -> Result<bool> {
+    let txn = db.begin().await?;
+
+    let booking = booking::Entity::find_by_id(booking_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .filter(|booking| booking.status == BookingStatus::PendingPayment);
+    let Some(booking) = booking else {
+        txn.rollback().await?;
+        return Ok(false);
+    };
+
+    release_seats(&txn, booking_id).await?;
+
+    let mut booking = booking.into_active_model();
+    booking.status = Set(BookingStatus::PaymentFailed);
+    if is_authorised {
+        booking.refund_status = Set(RefundStatus::Pending);
+    }
+    booking.updated_at = Set(Utc::now().naive_utc());
+    booking.update(&txn).await?;
+
+    txn.commit().await?;
+
+    Ok(true)
+}
+
+/// Pays back the payment of a booking whose refund is pending, or releases it when it was never
+/// captured. Refunds that fail stay pending, to be paid back by the box office.
+async fn refund_payment(
+    db: &DatabaseConnection,
+    payments: &PaymentGateway,
+    booking_id: Uuid,
+    intent_id: &str,
+) -> Result<()> {
+    match payments.refund(intent_id).await {
+        Ok(_) => {
+            booking::ActiveModel {
+                id: Set(booking_id),
+                refund_status: Set(RefundStatus::Refunded),
+                ..Default::default()
+            }
+            .update(db)
+            .await?;
+        }
+        Err(err) => log::error!("Failed to refund booking {booking_id}: {err}"),
+    }
+
+    Ok(())
+}
+
+/// Gives up on a booking that is waiting for its payment: fails it, and releases its payment if
+/// one was authorised. Returns whether the booking was given up on.
+async fn abandon_booking(
+    db: &DatabaseConnection,
+    payments: &PaymentGateway,
+    booking_id: Uuid,
+    intent_id: Option<&str>,
+) -> Result<bool> {
+    if !fail_booking(db, booking_id, intent_id.is_some()).await? {
+        return Ok(false);
+    }
+
+    if let Some(intent_id) = intent_id {
+        refund_payment(db, payments, booking_id, intent_id).await?;
+    }
+
+    Ok(true)
+}
+
+/// Gives up on bookings that have been waiting for their payment for longer than `max_age`,
+/// which only happens when checkout was interrupted, e.g. by a restart.
+pub async fn release_stale_pending_bookings(
+    db: &DatabaseConnection,
+    payments: &PaymentGateway,
+    max_age: Duration,
+) -> Result<u64> {
+    let stale_bookings = booking::Entity::find()
+        .filter(booking::Column::Status.eq(BookingStatus::PendingPayment))
+        .filter(booking::Column::UpdatedAt.lt(Utc::now().naive_utc() - max_age))
+        .all(db)
+        .await?;
+
+    let mut released = 0;
+    for booking in stale_bookings {
+        if abandon_booking(
+            db,
+            payments,
+            booking.id,
+            booking.payment_intent_id.as_deref(),
+        )
+        .await?
+        {
+            released += 1;
+        }
+    }
+
+    Ok(released)
+}
+
+pub fn spawn_pending_booking_reaper(
+    db: DatabaseConnection,
+    payments: PaymentGateway,
+    every: std::time::Duration,
+    max_age: Duration,
+) {
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(every);
+
+        loop {
+            interval.tick().await;
+
+            match release_stale_pending_bookings(&db, &payments, max_age).await {
+                Ok(0) => {}
+                Ok(released) => log::info!("Released {released} stale pending bookings"),
+                Err(err) => err.log_error(),
+            }
+        }
+    });
+}
+
+/// Checks that none of the seats are sold or held by someone else.
+async fn find_unavailable_seats(
+    txn: &DatabaseTransaction,
+    showtime_id: Uuid,
+    showtime_room_id: i32,
+    seats: &[String],
+    user_id: Uuid,
+    hold_id: Option<Uuid>,
+) -> Result<Vec<String>> {
+    let mut unavailable_seats = taken_seat::Entity::find()
+        .filter(taken_seat::Column::ShowtimeId.eq(showtime_id))
+        .filter(taken_seat::Column::ShowtimeRoomId.eq(showtime_room_id))
+        .filter(taken_seat::Column::SeatIdentifier.is_in(seats))
+        .all(txn)
+        .await?
+        .into_iter()
+        .map(|ts| ts.seat_identifier)
+        .collect::<Vec<_>>();
+
+    for seat in find_held_seats(txn, showtime_room_id, seats, user_id, hold_id).await? {
+        if !unavailable_seats.contains(&seat) {
+            unavailable_seats.push(seat);
+        }
+    }
+
+    Ok(unavailable_seats)
+}
+
+/// Marks the seats of the booking as taken, returning the seats that are sold to the booking.
+/// Must run while the showtime room is locked. Their tickets are only issued once the booking is
+/// confirmed.
+pub async fn sell_seats(
+    txn: &DatabaseTransaction,
+    booking: &booking::Model,
+) -> Result<Vec<String>> {
+    // Seats that violate `uq_taken_seat_showtime_id_seat_identifier` are skipped rather than
+    // aborting the statement, so only the seats that were free go to the booking.
+    taken_seat::Entity::insert_many(booking.seats.iter().map(|seat| taken_seat::ActiveModel {
+        showtime_id: Set(booking.showtime_id),
+        showtime_room_id: Set(booking.showtime_room_id),
+        seat_identifier: Set(seat.to_owned()),
+        booking_id: Set(Some(booking.id)),
+        ..Default::default()
+    }))
+    .on_conflict(
+        OnConflict::columns([
+            taken_seat::Column::ShowtimeId,
+            taken_seat::Column::ShowtimeRoomId,
+            taken_seat::Column::SeatIdentifier,
+        ])
+        .do_nothing()
+        .to_owned(),
+    )
+    .do_nothing()
+    .exec_without_returning(txn)
+    .await?;
+
+    // Seats sold to the booking earlier, e.g. by checkout before a payment webhook for it came
+    // in, are the booking's as well
+    let sold_seats = taken_seat::Entity::find()
+        .filter(taken_seat::Column::BookingId.eq(booking.id))
+        .all(txn)
+        .await?
+        .into_iter()
+        .map(|row| row.seat_identifier)
+        .collect();
+
+    Ok(sold_seats)
+}
+
+/// Sells the seats of a booking whose payment is authorised, then captures the payment. The
+/// showtime room is only locked while the seats are sold, not while waiting on the provider, so
+/// a slow provider does not hold up checkout for the whole room.
+async fn complete_booking(
+    db: &DatabaseConnection,
+    payments: &PaymentGateway,
+    booking: booking::Model,
+    intent_id: &str,
+    user_id: Uuid,
+    hold_id: Option<Uuid>,
+) -> Result<booking::Model> {
+    let mut pending_booking = booking.into_active_model();
+    pending_booking.payment_intent_id = Set(Some(intent_id.to_string()));
+    let booking = pending_booking.update(db).await?;
+
+    let txn = db.begin().await?;
+
+    lock_showtime_room(&txn, booking.showtime_id, booking.showtime_room_id).await?;
+
+    let sold_seats = sell_seats(&txn, &booking).await?;
+
+    // Someone may have held the seats while the payment was being authorised
+    let held_seats = find_held_seats(
+        &txn,
+        booking.showtime_room_id,
+        &booking.seats,
+        user_id,
+        hold_id,
+    )
+    .await?;
+
+    let taken_seats = booking
+        .seats
+        .iter()
+        .filter(|seat| !sold_seats.contains(seat) || held_seats.contains(seat))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if !taken_seats.is_empty() {
+        txn.rollback().await?;
+        return Err(AppError::SeatsTaken(taken_seats));
+    }
+
+    // The user's holds have served their purpose once the seats are sold: the hold booked under,
+    // or without one the holds on the booked seats.
+    let sold_holds = Condition::all().add(seat_hold::Column::UserId.eq(user_id));
+    let sold_holds = match hold_id {
+        Some(hold_id) => sold_holds.add(seat_hold::Column::HoldId.eq(hold_id)),
+        None => sold_holds
+            .add(seat_hold::Column::ShowtimeRoomId.eq(booking.showtime_room_id))
+            .add(seat_hold::Column::SeatIdentifier.is_in(&booking.seats)),
+    };
+    seat_hold::Entity::delete_many()
+        .filter(sold_holds)
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+
+    payments.capture(intent_id).await?;
+
+    let txn = db.begin().await?;
+
+    let booking = booking::Entity::find_by_id(booking.id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("Booking with id: {} does not exist", booking.id))
+        })?;
+
+    let booking = match booking.status {
+        BookingStatus::PendingPayment => {
+            let mut paid_booking = booking.into_active_model();
+            paid_booking.status = Set(BookingStatus::Confirmed);
+            paid_booking.updated_at = Set(Utc::now().naive_utc());
+            let booking = paid_booking.update(&txn).await?;
+
+            issue_tickets(&txn, booking.id).await?;
+
+            booking
+        }
+        // The payment webhook got to it first
+        BookingStatus::Confirmed => booking,
+        // The booking was given up on, e.g. by the pending booking reaper, while its payment was
+        // captured, so the customer is paid back unless that is already under way
+        _ => {
+            let booking_id = booking.id;
+
+            if booking.refund_status == RefundStatus::None {
+                let mut failed_booking = booking.into_active_model();
+                failed_booking.refund_status = Set(RefundStatus::Pending);
+                failed_booking.updated_at = Set(Utc::now().naive_utc());
+                failed_booking.update(&txn).await?;
+                txn.commit().await?;
+
+                refund_payment(db, payments, booking_id, intent_id).await?;
+            }
+
+            return Err(AppError::Conflict(format!(
+                "Booking with id: {} was given up on while it was being paid for",
+                booking_id
+            )));
+        }
+    };
+
+    txn.commit().await?;
+
+    Ok(booking)
+}
+
+/// Books the seats once their payment is captured. The booking is recorded as pending while
+/// the payment is authorised and captured, and whenever it does not go through the booking is
+/// failed, its seats are released and the payment is paid back.
+pub async fn create_booking(
+    db: &DatabaseConnection,
+    payments: &PaymentGateway,
+    user_id: Uuid,
+    showtime_id: String,
+    showtime_room_id: i32,
+    request: CreateBookingRequest,
+) -> Result<Booking> {
+    let showtime_id = Uuid::from_str(&showtime_id)?;
+    let hold_id = request
+        .hold_id
+        .map(|hold_id| Uuid::from_str(&hold_id))
+        .transpose()?;
+    let seats = parse_seats(&request.seats)?;
+
+    let txn = db.begin().await?;
+
+    let showtime_room = lock_showtime_room(&txn, showtime_id, showtime_room_id).await?;
+    let room = find_room(&txn, &showtime_room).await?;
+    validate_seats_in_room(&room, &seats)?;
+
+    let unavailable_seats = find_unavailable_seats(
+        &txn,
+        showtime_id,
+        showtime_room_id,
+        &seats,
+        user_id,
+        hold_id,
+    )
+    .await?;
+    if !unavailable_seats.is_empty() {
+        txn.rollback().await?;
+        return Err(AppError::SeatsTaken(unavailable_seats));
+    }
+
+    let now = Utc::now().naive_utc();
+    let booking = booking::ActiveModel {
+        id: Set(Uuid::now_v7()),
+        showtime_id: Set(showtime_id),
+        showtime_room_id: Set(showtime_room_id),
+        user_id: Set(Some(user_id)),
+        seats: Set(seats.clone()),
+        amount: Set(showtime_room.price * seats.len() as i32),
+        status: Set(BookingStatus::PendingPayment),
+        refund_status: Set(RefundStatus::None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        payment_provider: Set(Some(payments.provider_name().to_string())),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+
+    txn.commit().await?;
+
+    let booking_id = booking.id;
+    let intent = match payments
+        .create_intent(CreatePaymentIntent {
+            booking_id,
+            amount: booking.amount,
+            payment_method: request.payment_method,
+        })
+        .await
+    {
+        Ok(intent) => intent,
+        Err(err) => {
+            fail_booking(db, booking_id, false).await?;
+            return Err(err.into());
+        }
+    };
+
+    match complete_booking(db, payments, booking, &intent.id, user_id, hold_id).await {
+        Ok(booking) => Ok(map_booking(booking)),
+        Err(err) => {
+            // Bookings that cannot be given up on now are picked up by the pending booking reaper
+            if let Err(abandon_err) =
+                abandon_booking(db, payments, booking_id, Some(&intent.id)).await
+            {
+                abandon_err.log_error();
+            }
+            Err(err)
+        }
+    }
+}
+
+pub async fn get_booking(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    booking_id: String,
+) -> Result<Booking> {
+    let booking_id = Uuid::from_str(&booking_id)?;
+
+    let booking = booking::Entity::find_by_id(booking_id)
+        .filter(booking::Column::UserId.eq(user_id))
+        .one(db)
+        .await?;
+
+    match booking {
+        Some(booking) => Ok(map_booking(booking)),
+        None => Err(AppError::NotFound(format!(
+            "Booking with id: {} does not exist",
+            booking_id
+        ))),
+    }
+}
+
+pub async fn cancel_booking(
+    db: &DatabaseConnection,
+    payments: &PaymentGateway,
+    cutoff_minutes: i64,
+    user_id: Uuid,
+    booking_id: String,
+    request: CancelBookingRequest,
+) -> Result<Booking> {
+    let booking_id = Uuid::from_str(&booking_id)?;
+
+    let txn = db.begin().await?;
+
+    let booking = booking::Entity::find_by_id(booking_id)
+        .filter(booking::Column::UserId.eq(user_id))
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("Booking with id: {} does not exist", booking_id))
+        })?;
+
+    match booking.status {
+        BookingStatus::Confirmed => {}
+        BookingStatus::Cancelled => {
+            return Err(AppError::Conflict(format!(
+                "Booking with id: {} is already cancelled",
+                booking_id
+            )));
+        }
+        BookingStatus::PendingPayment | BookingStatus::PaymentFailed => {
+            return Err(AppError::Conflict(format!(
+                "Booking with id: {} has not been paid for",
+                booking_id
+            )));
+        }
+    }
+
+    let showtime_room =
+        lock_showtime_room(&txn, booking.showtime_id, booking.showtime_room_id).await?;
+
+    let now = Utc::now();
+    if showtime_room.time - Duration::minutes(cutoff_minutes) <= now {
+        return Err(AppError::Conflict(format!(
+            "Bookings can no longer be cancelled less than {} minutes before the showtime",
+            cutoff_minutes
+        )));
+    }
+
+    // Release the seats so they can be booked again
+    release_seats(&txn, booking.id).await?;
+
+    let refund_status = if booking.amount > 0 {
+        RefundStatus::Pending
+    } else {
+        RefundStatus::None
+    };
+
+    let mut booking = booking.into_active_model();
+    booking.status = Set(BookingStatus::Cancelled);
+    booking.refund_status = Set(refund_status);
+    booking.cancellation_reason = Set(request
+        .reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty()));
+    booking.cancelled_at = Set(Some(now.naive_utc()));
+    booking.updated_at = Set(now.naive_utc());
+
+    let booking = booking.update(&txn).await?;
+
+    txn.commit().await?;
+
+    // Refunds that fail stay pending, to be paid back by the box office
+    let booking = match booking.payment_intent_id.to_owned() {
+        Some(intent_id) if booking.refund_status == RefundStatus::Pending => {
+            match payments.refund(&intent_id).await {
+                Ok(intent) => {
+                    log::info!("Refunded {} for booking {booking_id}", intent.amount);
+                    let mut refunded_booking = booking.into_active_model();
+                    refunded_booking.refund_status = Set(RefundStatus::Refunded);
+                    refunded_booking.update(db).await?
+                }
+                Err(err) => {
+                    log::error!("Failed to refund booking {booking_id}: {err}");
+                    booking
+                }
+            }
+        }
+        _ => booking,
+    };
+
+    Ok(map_booking(booking))
+}
+
+#[derive(FromQueryResult)]
+struct BookingHistoryRow {
+    id: Uuid,
+    showtime_id: Uuid,
+    showtime_room_id: i32,
+    seats: Vec<String>,
+    amount: i32,
+    status: BookingStatus,
+    refund_status: RefundStatus,
+    cancelled_at: Option<NaiveDateTime>,
+    created_at: NaiveDateTime,
+    time: DateTimeWithTimeZone,
+    room_name: String,
+    theater_id: Uuid,
+    theater_name: String,
+    theater_location: String,
+    timezone: String,
+    movie_id: Uuid,
+    movie_title: String,
+    poster_url: String,
+}
+
+fn map_booking_history(row: BookingHistoryRow) -> Result<BookingHistory> {
+    let time = row.time.with_timezone(&Utc);
+
+    Ok(BookingHistory {
+        id: row.id.to_string(),
+        showtime_id: row.showtime_id.to_string(),
+        showtime_room_id: row.showtime_room_id,
+        movie: BookedMovie {
+            id: row.movie_id.to_string(),
+            title: row.movie_title,
+            poster_url: row.poster_url,
+        },
+        local_time: to_local_time(time, &row.timezone)?,
+        theater: BookedTheater {
+            id: row.theater_id.to_string(),
+            name: row.theater_name,
+            location: row.theater_location,
+            timezone: row.timezone,
+        },
+        room_name: row.room_name,
+        time,
+        seats: row.seats,
+        amount: row.amount,
+        status: row.status.to_value(),
+        refund_status: row.refund_status.to_value(),
+        cancelled_at: row.cancelled_at,
+        created_at: row.created_at,
+    })
+}
+
+/// The bookings of the user, leaving out the ones whose payment failed as nothing was bought.
+pub async fn get_my_bookings(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    options: GetMyBookingsQueryParams,
+) -> Result<(Vec<BookingHistory>, Info)> {
+    let mut errors = vec![];
+
+    validate_pagination(options.limit, options.page, &mut errors);
+
+    let upcoming = match options.when.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some("upcoming") => Some(true),
+        Some("past") => Some(false),
+        Some(when) => {
+            errors.push(FieldError {
+                field: "when".to_string(),
+                message: format!("{when} is not one of upcoming or past"),
+            });
+            None
+        }
+    };
+
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let now = Utc::now();
+    let order = match upcoming {
+        Some(true) => Order::Asc,
+        _ => Order::Desc,
+    };
+
+    let query = booking::Entity::find()
+        .select_only()
+        .columns([
+            booking::Column::Id,
+            booking::Column::ShowtimeId,
+            booking::Column::ShowtimeRoomId,
+            booking::Column::Seats,
+            booking::Column::Amount,
+            booking::Column::Status,
+            booking::Column::RefundStatus,
+            booking::Column::CancelledAt,
+            booking::Column::CreatedAt,
+        ])
+        .column_as(showtime_room::Column::Time, "time")
+        .column_as(room::Column::Name, "room_name")
+        .column_as(theater::Column::Id, "theater_id")
+        .column_as(theater::Column::Name, "theater_name")
+        .column_as(theater::Column::Location, "theater_location")
+        .column_as(theater::Column::Timezone, "timezone")
+        .column_as(movie::Column::Id, "movie_id")
+        .column_as(movie::Column::Title, "movie_title")
+        .column_as(movie::Column::PosterUrl, "poster_url")
+        .join(JoinType::InnerJoin, booking::Relation::ShowtimeRoom.def())
+        .join(JoinType::InnerJoin, showtime_room::Relation::Room.def())
+        .join(JoinType::InnerJoin, room::Relation::Theater.def())
+        .join(JoinType::InnerJoin, booking::Relation::Showtime.def())
+        .join(JoinType::InnerJoin, showtime::Relation::Movie.def())
+        .filter(booking::Column::UserId.eq(user_id))
+        .filter(booking::Column::Status.ne(BookingStatus::PaymentFailed))
+        .apply_if(upcoming, |query, upcoming| {
+            if upcoming {
+                query.filter(showtime_room::Column::Time.gte(now))
+            } else {
+                query.filter(showtime_room::Column::Time.lt(now))
+            }
+        })
+        .order_by(showtime_room::Column::Time, order.to_owned())
+        .order_by(booking::Column::Id, order);
+
+    let total_items = query.clone().count(db).await?;
+
+    let bookings = query
+        .into_model::<BookingHistoryRow>()
+        .paginate(db, options.limit)
+        .fetch_page(options.page - 1)
+        .await?
+        .into_iter()
+        .map(map_booking_history)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((
+        bookings,
+        Info::new(options.page, options.limit, total_items),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use entity::{
+        booking, room,
+        sea_orm_active_enums::{BookingStatus, RefundStatus},
+        seat_hold, showtime_room, taken_seat,
+    };
+    use sea_orm::{DatabaseBackend, DatabaseConnection, DbErr, MockDatabase, MockExecResult};
+    use std::{sync::Arc, time::Duration};
+    use uuid::Uuid;
+
+    use crate::{
+        app_error::AppError,
+        config::MockPaymentOutcome,
+        models::requests::create_booking_request_model::CreateBookingRequest,
+        payments::{PaymentGateway, PaymentStatus, mock_payment_provider::MockPaymentProvider},
+    };
+
+    use super::create_booking;
+
+    const SHOWTIME_ROOM_ID: i32 = 1;
+
+    struct Checkout {
+        showtime_room: showtime_room::Model,
+        room: room::Model,
+        booking: booking::Model,
+        taken_seat: taken_seat::Model,
+    }
+
+    impl Checkout {
+        fn new() -> Self {
+            let now = Utc::now();
+            let showtime_id = Uuid::now_v7();
+            let room_id = Uuid::now_v7();
+            let booking_id = Uuid::now_v7();
+
+            Self {
+                showtime_room: showtime_room::Model {
+                    id: SHOWTIME_ROOM_ID,
+                    time: now.fixed_offset(),
+                    price: 50_000,
+                    room_id,
+                    showtime_id,
+                },
+                room: room::Model {
+                    id: room_id,
+                    name: "Studio 1".to_string(),
+                    capacity: 4,
+                    max_rows: 2,
+                    max_columns: 2,
+                    theater_id: Uuid::now_v7(),
+                    layout: None,
+                },
+                booking: booking::Model {
+                    id: booking_id,
+                    showtime_id,
+                    showtime_room_id: SHOWTIME_ROOM_ID,
+                    user_id: Some(Uuid::now_v7()),
+                    seats: vec!["A1".to_string()],
+                    amount: 50_000,
+                    status: BookingStatus::PendingPayment,
+                    refund_status: RefundStatus::None,
+                    cancellation_reason: None,
+                    cancelled_at: None,
+                    created_at: now.naive_utc(),
+                    updated_at: now.naive_utc(),
+                    payment_provider: Some("mock".to_string()),
+                    payment_intent_id: None,
+                },
+                taken_seat: taken_seat::Model {
+                    id: 1,
+                    showtime_id,
+                    showtime_room_id: SHOWTIME_ROOM_ID,
+                    seat_identifier: "A1".to_string(),
+                    booking_id: Some(booking_id),
+                },
+            }
+        }
+
+        fn intent_id(&self) -> String {
+            format!("mock_pi_{}", self.booking.id.simple())
+        }
+
+        fn with_intent(&self) -> booking::Model {
+            booking::Model {
+                payment_intent_id: Some(self.intent_id()),
+                ..self.booking.clone()
+            }
+        }
+
+        fn failed(&self) -> booking::Model {
+            booking::Model {
+                status: BookingStatus::PaymentFailed,
+                ..self.with_intent()
+            }
+        }
+
+        /// Recording the pending booking, up to asking the provider to authorise its payment.
+        fn start(&self, db: MockDatabase) -> MockDatabase {
+            db.append_query_results([vec![self.showtime_room.clone()]])
+                .append_query_results([vec![self.room.clone()]])
+                .append_query_results([Vec::<taken_seat::Model>::new()])
+                .append_query_results([Vec::<seat_hold::Model>::new()])
+                .append_query_results([vec![self.booking.clone()]])
+        }
+
+        /// Selling the seats of the authorised booking, up to capturing its payment.
+        fn sell(&self, db: MockDatabase) -> MockDatabase {
+            db.append_query_results([vec![self.with_intent()]])
+                .append_query_results([vec![self.showtime_room.clone()]])
+                .append_exec_results([MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                }])
+                .append_query_results([vec![self.taken_seat.clone()]])
+                .append_query_results([Vec::<seat_hold::Model>::new()])
+                .append_exec_results([MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 0,
+                }])
+        }
+
+        /// Recording that the payment of the failed booking was paid back.
+        fn refund(&self, db: MockDatabase) -> MockDatabase {
+            db.append_query_results([vec![booking::Model {
+                refund_status: RefundStatus::Refunded,
+                ..self.failed()
+            }]])
+        }
+
+        /// Failing the booking, voiding its tickets and releasing its seats.
+        fn fail(&self, db: MockDatabase) -> MockDatabase {
+            db.append_query_results([vec![self.with_intent()]])
+                .append_exec_results([
+                    MockExecResult {
+                        last_insert_id: 0,
+                        rows_affected: 1,
+                    },
+                    MockExecResult {
+                        last_insert_id: 0,
+                        rows_affected: 1,
+                    },
+                ])
+                .append_query_results([vec![self.failed()]])
+        }
+
+        fn request(payment_method: &str) -> CreateBookingRequest {
+            CreateBookingRequest {
+                seats: vec!["A1".to_string()],
+                hold_id: None,
+                payment_method: Some(payment_method.to_string()),
+            }
+        }
+
+        async fn checkout(
+            &self,
+            db: &DatabaseConnection,
+            provider: &Arc<MockPaymentProvider>,
+            payment_method: &str,
+        ) -> crate::app_state::Result<crate::models::booking_model::Booking> {
+            let payments =
+                PaymentGateway::with_provider(provider.clone(), Duration::from_millis(50));
+
+            create_booking(
+                db,
+                &payments,
+                self.booking.user_id.unwrap(),
+                self.booking.showtime_id.to_string(),
+                SHOWTIME_ROOM_ID,
+                Self::request(payment_method),
+            )
+            .await
+        }
+    }
+
+    fn provider() -> Arc<MockPaymentProvider> {
+        Arc::new(MockPaymentProvider::new(MockPaymentOutcome::Succeed))
+    }
+
+    fn transaction_log(db: DatabaseConnection) -> String {
+        format!("{:?}", db.into_transaction_log())
+    }
+
+    #[actix_web::test]
+    async fn declined_checkout_fails_the_booking() {
+        let checkout = Checkout::new();
+        let db = checkout.start(MockDatabase::new(DatabaseBackend::Postgres));
+        let db = checkout.fail(db).into_connection();
+        let provider = provider();
+
+        let result = checkout.checkout(&db, &provider, "mock_decline").await;
+
+        assert!(matches!(result, Err(AppError::PaymentDeclined(_))));
+        assert_eq!(
+            provider.intent_status(&checkout.intent_id()),
+            Some(PaymentStatus::Declined)
+        );
+        assert!(transaction_log(db).contains("payment_failed"));
+    }
+
+    #[actix_web::test]
+    async fn timed_out_capture_fails_the_booking_and_releases_the_payment() {
+        let checkout = Checkout::new();
+        let db = checkout.start(MockDatabase::new(DatabaseBackend::Postgres));
+        let db = checkout.sell(db);
+        let db = checkout.refund(checkout.fail(db)).into_connection();
+        let provider = provider();
+
+        let result = checkout
+            .checkout(&db, &provider, "mock_timeout_capture")
+            .await;
+
+        assert!(matches!(result, Err(AppError::PaymentUnavailable(_))));
+        assert_eq!(
+            provider.intent_status(&checkout.intent_id()),
+            Some(PaymentStatus::Refunded)
+        );
+        let log = transaction_log(db);
+        assert!(log.contains("payment_failed"));
+        assert!(log.contains("refunded"));
+    }
+
+    #[actix_web::test]
+    async fn checkout_failing_after_capture_refunds_the_payment() {
+        let checkout = Checkout::new();
+        let db = checkout.start(MockDatabase::new(DatabaseBackend::Postgres));
+        let db = checkout
+            .sell(db)
+            .append_query_errors([DbErr::Custom("connection reset".to_string())]);
+        let db = checkout.refund(checkout.fail(db)).into_connection();
+        let provider = provider();
+
+        let result = checkout.checkout(&db, &provider, "mock_succeed").await;
+
+        assert!(matches!(result, Err(AppError::Database(_))));
+        assert_eq!(
+            provider.intent_status(&checkout.intent_id()),
+            Some(PaymentStatus::Refunded)
+        );
+        let log = transaction_log(db);
+        assert!(log.contains("payment_failed"));
+        assert!(log.contains("refunded"));
+    }
+
+    #[actix_web::test]
+    async fn capture_of_a_booking_given_up_on_meanwhile_refunds_the_payment() {
+        let checkout = Checkout::new();
+        let db = checkout.start(MockDatabase::new(DatabaseBackend::Postgres));
+        let db = checkout
+            .sell(db)
+            .append_query_results([
+                // Given up on while its payment was captured
+                vec![checkout.failed()],
+                // Its refund is recorded as pending, then as done
+                vec![checkout.failed()],
+                vec![checkout.failed()],
+                // So checkout has nothing left to give up on
+                vec![checkout.failed()],
+            ])
+            .into_connection();
+        let provider = provider();
+
+        let result = checkout.checkout(&db, &provider, "mock_succeed").await;
+
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+        assert_eq!(
+            provider.intent_status(&checkout.intent_id()),
+            Some(PaymentStatus::Refunded)
+        );
+        assert!(transaction_log(db).contains("refunded"));
+    }
+}
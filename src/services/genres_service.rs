@@ -0,0 +1,49 @@
+use entity::{genre, movie_genre};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{app_state::Result, models::genre_model::Genre};
+
+pub async fn get_genres(db: &DatabaseConnection) -> Result<Vec<Genre>> {
+    let genres = genre::Entity::find()
+        .order_by_asc(genre::Column::Name)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|g| Genre {
+            id: g.id,
+            name: g.name,
+        })
+        .collect();
+
+    Ok(genres)
+}
+
+/// The genre names of each of the movies, sorted by name.
+pub async fn find_movie_genres<C: ConnectionTrait>(
+    db: &C,
+    movie_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<String>>> {
+    let mut genres: HashMap<Uuid, Vec<String>> = HashMap::new();
+
+    let movie_genres = movie_genre::Entity::find()
+        .filter(movie_genre::Column::MovieId.is_in(movie_ids.to_vec()))
+        .find_also_related(genre::Entity)
+        .order_by_asc(genre::Column::Name)
+        .all(db)
+        .await?;
+
+    for (movie_genre, genre) in movie_genres {
+        if let Some(genre) = genre {
+            genres
+                .entry(movie_genre.movie_id)
+                .or_default()
+                .push(genre.name);
+        }
+    }
+
+    Ok(genres)
+}
@@ -1,3 +1,13 @@
+pub mod auth_service;
+pub mod booking_service;
+pub mod genres_service;
 pub mod movies_service;
+pub mod pagination_service;
+pub mod payment_webhook_service;
+pub mod rooms_service;
+pub mod seat_hold_service;
+pub mod seat_map_service;
 pub mod showtime_service;
 pub mod theaters_service;
+pub mod ticket_service;
+pub mod timezone_service;
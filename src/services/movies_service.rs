@@ -1,70 +1,602 @@
 use anyhow::anyhow;
-use entity::movie;
-use sea_orm::{DatabaseConnection, EntityTrait, PaginatorTrait, QueryOrder};
-use serde::Serialize;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{NaiveDate, Utc};
+use entity::{genre, movie, movie_genre, showtime, showtime_room};
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::Set,
+    ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait, IntoActiveModel,
+    Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait, raw_sql,
+    sea_query::{Expr, ExprTrait, Query},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::str::FromStr;
 use uuid::Uuid;
 
 use crate::{
-    app_error::AppError,
+    app_error::{AppError, FieldError},
     app_state::Result,
-    models::{movie_model::Movie, requests::get_movies_request_model::GetMoviesQueryParams},
+    models::{
+        movie_model::Movie,
+        requests::{
+            create_movie_request_model::CreateMovieRequest,
+            get_movies_request_model::GetMoviesQueryParams,
+            search_movies_request_model::SearchMoviesQueryParams,
+            update_movie_request_model::UpdateMovieRequest,
+        },
+    },
+};
+
+use super::{
+    genres_service::find_movie_genres,
+    pagination_service::{Info, validate_pagination},
 };
 
+const MAX_RATING: f32 = 10.0;
+
+pub const MAX_RUNTIME_MINUTES: i32 = 600;
+
+const MAX_SEARCH_LIMIT: u64 = 50;
+
+// Queries up to this many characters fall back to trigram matching on the title when full-text
+// search finds nothing, which catches typos in short titles
+const TRIGRAM_FALLBACK_MAX_LEN: usize = 32;
+
+const TRIGRAM_THRESHOLD: f32 = 0.3;
+
+// `movie.age_rating` is a varchar(10)
+const MAX_AGE_RATING_LEN: usize = 10;
+
+fn map_movie(movie: movie::Model, genres: Vec<String>) -> Movie {
+    Movie {
+        id: movie.id.to_string(),
+        title: movie.title,
+        overview: movie.overview,
+        rating: movie.rating,
+        genres,
+        poster_url: movie.poster_url,
+        runtime_minutes: movie.runtime_minutes,
+        release_date: movie.release_date,
+        age_rating: movie.age_rating,
+        language: movie.language,
+        trailer_url: movie.trailer_url,
+        director: movie.director,
+        cast: movie.cast_members,
+    }
+}
+
+struct MovieFields {
+    title: String,
+    overview: String,
+    rating: f32,
+    genres: Vec<String>,
+    poster_url: String,
+    runtime_minutes: Option<i32>,
+    release_date: Option<NaiveDate>,
+    age_rating: Option<String>,
+    language: Option<String>,
+    trailer_url: Option<String>,
+    director: Option<String>,
+    cast: Vec<String>,
+}
+
+fn is_valid_http_url(url: &str) -> bool {
+    let host = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .and_then(|rest| rest.split('/').next());
+
+    host.is_some_and(|host| !host.is_empty()) && !url.chars().any(char::is_whitespace)
+}
+
+pub fn normalize_text(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+// Resolves the genre names case-insensitively against the known genres, keeping each genre once.
+fn resolve_genres(
+    names: &[String],
+    known_genres: &[genre::Model],
+    errors: &mut Vec<FieldError>,
+) -> Vec<genre::Model> {
+    if names.is_empty() {
+        errors.push(FieldError {
+            field: "genres".to_string(),
+            message: "At least one genre is required".to_string(),
+        });
+    }
+
+    let mut genres: Vec<genre::Model> = vec![];
+    for (index, name) in names.iter().enumerate() {
+        match known_genres
+            .iter()
+            .find(|genre| genre.name.eq_ignore_ascii_case(name.trim()))
+        {
+            Some(genre) if genres.iter().any(|g| g.id == genre.id) => {}
+            Some(genre) => genres.push(genre.clone()),
+            None => errors.push(FieldError {
+                field: format!("genres[{index}]"),
+                message: format!("{} is not a known genre", name.trim()),
+            }),
+        }
+    }
+    genres.sort_by(|a, b| a.name.cmp(&b.name));
+
+    genres
+}
+
+fn validate_movie_fields(
+    fields: MovieFields,
+    known_genres: &[genre::Model],
+) -> Result<(MovieFields, Vec<genre::Model>)> {
+    let mut errors = vec![];
+
+    let title = fields.title.trim().to_string();
+    if title.is_empty() {
+        errors.push(FieldError {
+            field: "title".to_string(),
+            message: "Title must not be empty".to_string(),
+        });
+    }
+
+    if !(0.0..=MAX_RATING).contains(&fields.rating) {
+        errors.push(FieldError {
+            field: "rating".to_string(),
+            message: format!("Rating must be between 0 and {MAX_RATING}"),
+        });
+    }
+
+    let genres = resolve_genres(&fields.genres, known_genres, &mut errors);
+
+    let poster_url = fields.poster_url.trim().to_string();
+    if !is_valid_http_url(&poster_url) {
+        errors.push(FieldError {
+            field: "posterUrl".to_string(),
+            message: "Poster URL must be an http or https URL".to_string(),
+        });
+    }
+
+    if fields
+        .runtime_minutes
+        .is_some_and(|runtime| !(1..=MAX_RUNTIME_MINUTES).contains(&runtime))
+    {
+        errors.push(FieldError {
+            field: "runtimeMinutes".to_string(),
+            message: format!("Runtime must be between 1 and {MAX_RUNTIME_MINUTES} minutes"),
+        });
+    }
+
+    let age_rating = normalize_text(fields.age_rating);
+    if age_rating
+        .as_ref()
+        .is_some_and(|age_rating| age_rating.chars().count() > MAX_AGE_RATING_LEN)
+    {
+        errors.push(FieldError {
+            field: "ageRating".to_string(),
+            message: format!("Age rating must be at most {MAX_AGE_RATING_LEN} characters long"),
+        });
+    }
+
+    let trailer_url = normalize_text(fields.trailer_url);
+    if trailer_url
+        .as_ref()
+        .is_some_and(|trailer_url| !is_valid_http_url(trailer_url))
+    {
+        errors.push(FieldError {
+            field: "trailerUrl".to_string(),
+            message: "Trailer URL must be an http or https URL".to_string(),
+        });
+    }
+
+    let cast = fields
+        .cast
+        .iter()
+        .map(|member| member.trim().to_string())
+        .collect::<Vec<_>>();
+    for (index, member) in cast.iter().enumerate() {
+        if member.is_empty() {
+            errors.push(FieldError {
+                field: format!("cast[{index}]"),
+                message: "Cast member names must not be empty".to_string(),
+            });
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors));
+    }
+
+    Ok((
+        MovieFields {
+            title,
+            overview: fields.overview.trim().to_string(),
+            rating: fields.rating,
+            genres: genres.iter().map(|genre| genre.name.to_owned()).collect(),
+            poster_url,
+            runtime_minutes: fields.runtime_minutes,
+            release_date: fields.release_date,
+            age_rating,
+            language: normalize_text(fields.language),
+            trailer_url,
+            director: normalize_text(fields.director),
+            cast,
+        },
+        genres,
+    ))
+}
+
+/// Columns the movie list can be sorted by.
+const SORTABLE_COLUMNS: [&str; 6] = [
+    "id",
+    "title",
+    "overview",
+    "rating",
+    "runtime_minutes",
+    "release_date",
+];
+
 fn string_to_column(str: &str) -> core::result::Result<movie::Column, anyhow::Error> {
     match str {
         "id" => Ok(movie::Column::Id),
         "title" => Ok(movie::Column::Title),
         "overview" => Ok(movie::Column::Overview),
-        "genre" => Ok(movie::Column::Genre),
         "rating" => Ok(movie::Column::Rating),
-        _ => Err(anyhow!("{str} is not a valid movie column")),
+        "runtime_minutes" => Ok(movie::Column::RuntimeMinutes),
+        "release_date" => Ok(movie::Column::ReleaseDate),
+        // A movie can have several genres now, so there is no single genre to sort it by
+        "genre" => Err(anyhow!(
+            "Sorting by genre is no longer supported as movies can have several genres, \
+             sort by one of {} instead",
+            SORTABLE_COLUMNS.join(", ")
+        )),
+        _ => Err(anyhow!(
+            "{str} is not a sortable movie column, sort by one of {}",
+            SORTABLE_COLUMNS.join(", ")
+        )),
     }
 }
 
-#[derive(Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Info {
-    pub current_page: u64,
-    pub total_page: u64,
+/// Parses an optional query filter, recording a validation error for `field` when it is malformed.
+pub fn parse_filter<T: FromStr>(
+    field: &str,
+    value: Option<&String>,
+    expected: &str,
+    errors: &mut Vec<FieldError>,
+) -> Option<T> {
+    let value = value?.trim();
+    match value.parse() {
+        Ok(value) => Some(value),
+        Err(_) => {
+            errors.push(FieldError {
+                field: field.to_string(),
+                message: format!("{value} is not {expected}"),
+            });
+            None
+        }
+    }
+}
+
+// Turns the filters of the movie list into a condition, reporting every malformed filter at once.
+fn build_movie_filter(
+    options: &GetMoviesQueryParams,
+    known_genres: &[genre::Model],
+) -> Result<Condition> {
+    let mut errors = vec![];
+    let mut condition = Condition::all();
+
+    if let Some(genre) = &options.genre {
+        let mut genre_ids = vec![];
+        for name in genre.split(',') {
+            match known_genres
+                .iter()
+                .find(|known| known.name.eq_ignore_ascii_case(name.trim()))
+            {
+                Some(known) => genre_ids.push(known.id),
+                None => errors.push(FieldError {
+                    field: "genre".to_string(),
+                    message: format!("{} is not a known genre", name.trim()),
+                }),
+            }
+        }
+
+        condition = condition.add(
+            movie::Column::Id.in_subquery(
+                Query::select()
+                    .column(movie_genre::Column::MovieId)
+                    .from(movie_genre::Entity)
+                    .and_where(movie_genre::Column::GenreId.is_in(genre_ids))
+                    .to_owned(),
+            ),
+        );
+    }
+
+    let ratings = [
+        ("minRating", options.min_rating.as_ref()),
+        ("maxRating", options.max_rating.as_ref()),
+    ]
+    .map(|(field, value)| {
+        parse_filter::<f32>(field, value, "a number", &mut errors).filter(|rating| {
+            let in_range = (0.0..=MAX_RATING).contains(rating);
+            if !in_range {
+                errors.push(FieldError {
+                    field: field.to_string(),
+                    message: format!("Rating must be between 0 and {MAX_RATING}"),
+                });
+            }
+            in_range
+        })
+    });
+    if let [Some(min_rating), Some(max_rating)] = ratings
+        && min_rating > max_rating
+    {
+        errors.push(FieldError {
+            field: "minRating".to_string(),
+            message: "minRating must not be greater than maxRating".to_string(),
+        });
+    }
+    if let Some(min_rating) = ratings[0] {
+        condition = condition.add(movie::Column::Rating.gte(min_rating));
+    }
+    if let Some(max_rating) = ratings[1] {
+        condition = condition.add(movie::Column::Rating.lte(max_rating));
+    }
+
+    let release_window = [
+        ("releasedFrom", options.released_from.as_ref()),
+        ("releasedTo", options.released_to.as_ref()),
+    ]
+    .map(|(field, value)| {
+        parse_filter::<NaiveDate>(field, value, "a date in the format YYYY-MM-DD", &mut errors)
+    });
+    if let [Some(released_from), Some(released_to)] = release_window
+        && released_from > released_to
+    {
+        errors.push(FieldError {
+            field: "releasedFrom".to_string(),
+            message: "releasedFrom must not be after releasedTo".to_string(),
+        });
+    }
+    if let Some(released_from) = release_window[0] {
+        condition = condition.add(movie::Column::ReleaseDate.gte(released_from));
+    }
+    if let Some(released_to) = release_window[1] {
+        condition = condition.add(movie::Column::ReleaseDate.lte(released_to));
+    }
+
+    let now = Utc::now();
+
+    let now_showing = parse_filter::<bool>(
+        "nowShowing",
+        options.now_showing.as_ref(),
+        "true or false",
+        &mut errors,
+    );
+    if now_showing == Some(true) {
+        condition = condition.add(
+            movie::Column::Id.in_subquery(
+                Query::select()
+                    .column(showtime::Column::MovieId)
+                    .from(showtime::Entity)
+                    .inner_join(
+                        showtime_room::Entity,
+                        Expr::col((showtime_room::Entity, showtime_room::Column::ShowtimeId))
+                            .equals((showtime::Entity, showtime::Column::Id)),
+                    )
+                    .and_where(
+                        Expr::col((showtime_room::Entity, showtime_room::Column::Time)).gt(now),
+                    )
+                    .to_owned(),
+            ),
+        );
+    }
+
+    let coming_soon = parse_filter::<bool>(
+        "comingSoon",
+        options.coming_soon.as_ref(),
+        "true or false",
+        &mut errors,
+    );
+    if coming_soon == Some(true) {
+        condition = condition.add(movie::Column::ReleaseDate.gt(now.date_naive()));
+    }
+
+    if errors.is_empty() {
+        Ok(condition)
+    } else {
+        Err(AppError::Validation(errors))
+    }
+}
+
+// Position of the last movie of a page in the sort order. The id breaks ties between movies with
+// the same sort value, so the position stays valid while movies are inserted or removed.
+#[derive(Serialize, Deserialize)]
+struct MovieCursor {
+    sort: String,
+    value: serde_json::Value,
+    id: Uuid,
+}
+
+fn encode_cursor(movie: &movie::Model, sort: &str) -> String {
+    let value = match sort {
+        "title" => json!(movie.title),
+        "overview" => json!(movie.overview),
+        "rating" => json!(movie.rating),
+        "runtime_minutes" => json!(movie.runtime_minutes),
+        "release_date" => json!(movie.release_date),
+        _ => json!(movie.id),
+    };
+
+    let cursor = json!(MovieCursor {
+        sort: sort.to_string(),
+        value,
+        id: movie.id,
+    });
+
+    URL_SAFE_NO_PAD.encode(cursor.to_string())
+}
+
+// Decodes the cursor into the sort value and id of the movie it points at. A `None` sort value
+// stands for a movie without a value in a nullable sort column.
+fn decode_cursor(cursor: &str, sort: &str) -> Result<(Option<sea_orm::Value>, Uuid)> {
+    let invalid_cursor = |message: &str| {
+        AppError::Validation(vec![FieldError {
+            field: "after".to_string(),
+            message: message.to_string(),
+        }])
+    };
+
+    let cursor = URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<MovieCursor>(&bytes).ok())
+        .ok_or_else(|| invalid_cursor("Cursor is malformed"))?;
+
+    if cursor.sort != sort {
+        return Err(invalid_cursor(
+            "Cursor was issued for a different sort, start again without one",
+        ));
+    }
+
+    let value = match sort {
+        "title" | "overview" => {
+            serde_json::from_value::<String>(cursor.value).map(|value| Some(value.into()))
+        }
+        "rating" => serde_json::from_value::<f32>(cursor.value).map(|value| Some(value.into())),
+        "runtime_minutes" => {
+            serde_json::from_value::<Option<i32>>(cursor.value).map(|value| value.map(Into::into))
+        }
+        "release_date" => serde_json::from_value::<Option<NaiveDate>>(cursor.value)
+            .map(|value| value.map(Into::into)),
+        _ => serde_json::from_value::<Uuid>(cursor.value).map(|value| Some(value.into())),
+    }
+    .map_err(|_| invalid_cursor("Cursor is malformed"))?;
+
+    Ok((value, cursor.id))
+}
+
+// Matches the movies that come after the cursor position, following Postgres' default of sorting
+// NULLs last in ascending and first in descending order.
+fn after_cursor(
+    column: movie::Column,
+    order: &Order,
+    value: Option<sea_orm::Value>,
+    id: Uuid,
+) -> Condition {
+    match (order, value) {
+        (Order::Desc, Some(value)) => Condition::any().add(column.lt(value.clone())).add(
+            Condition::all()
+                .add(column.eq(value))
+                .add(movie::Column::Id.lt(id)),
+        ),
+        (Order::Desc, None) => Condition::any()
+            .add(
+                Condition::all()
+                    .add(column.is_null())
+                    .add(movie::Column::Id.lt(id)),
+            )
+            .add(column.is_not_null()),
+        (_, Some(value)) => Condition::any()
+            .add(column.gt(value.clone()))
+            .add(
+                Condition::all()
+                    .add(column.eq(value))
+                    .add(movie::Column::Id.gt(id)),
+            )
+            .add(column.is_null()),
+        (_, None) => Condition::all()
+            .add(column.is_null())
+            .add(movie::Column::Id.gt(id)),
+    }
 }
 
+/// Lists movies a page at a time, or after the `after` cursor when one is given.
 pub async fn get_movies(
     db: &DatabaseConnection,
     options: GetMoviesQueryParams,
 ) -> Result<(Vec<Movie>, Info)> {
+    let mut errors = vec![];
+    validate_pagination(options.limit, options.page, &mut errors);
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let known_genres = match options.genre {
+        Some(_) => genre::Entity::find().all(db).await?,
+        None => vec![],
+    };
+    let filter = build_movie_filter(&options, &known_genres)?;
+
     let sort_options = options.sort_options;
 
-    let sort_column_as_column = string_to_column(&sort_options.0)?;
+    let sort_column_as_column = string_to_column(&sort_options.0).map_err(|e| {
+        AppError::Validation(vec![FieldError {
+            field: "sort".to_string(),
+            message: e.to_string(),
+        }])
+    })?;
 
-    let paginator = movie::Entity::find()
-        .order_by(sort_column_as_column, sort_options.1)
-        .paginate(db, options.limit);
+    let query = movie::Entity::find()
+        .filter(filter)
+        .order_by(sort_column_as_column, sort_options.1.clone())
+        .order_by(movie::Column::Id, sort_options.1.clone());
 
-    let total_page = paginator.num_pages().await?;
-    let movies = paginator
-        .fetch_page(options.page)
-        .await?
-        .iter()
-        .map(|m| Movie {
-            id: m.id.to_string(),
-            title: m.title.to_owned(),
-            overview: m.overview.to_owned(),
-            genre: m.genre.to_owned(),
-            poster_url: m.poster_url.to_owned(),
-            rating: m.rating,
+    let total_items = query.clone().count(db).await?;
+
+    let (movies, info) = match options.after {
+        Some(after) => {
+            let (value, id) = decode_cursor(&after, &sort_options.0)?;
+            let after = after_cursor(sort_column_as_column, &sort_options.1, value, id);
+
+            // Any movie that is not after the cursor, including the one it points at, is on an
+            // earlier page. `IS NOT TRUE` keeps the ones the comparison is NULL for.
+            let has_prev = query
+                .clone()
+                .filter(Expr::cust_with_expr("($1) IS NOT TRUE", after.clone()))
+                .limit(1)
+                .one(db)
+                .await?
+                .is_some();
+
+            // One extra movie tells whether there is a next page
+            let mut movies = query.filter(after).limit(options.limit + 1).all(db).await?;
+
+            let has_next = movies.len() as u64 > options.limit;
+            movies.truncate(options.limit as usize);
+
+            (
+                movies,
+                Info::after_cursor(options.limit, total_items, has_next, has_prev),
+            )
+        }
+        None => {
+            let movies = query
+                .paginate(db, options.limit)
+                .fetch_page(options.page - 1)
+                .await?;
+
+            (movies, Info::new(options.page, options.limit, total_items))
+        }
+    };
+
+    let next_cursor = movies
+        .last()
+        .filter(|_| info.has_next)
+        .map(|movie| encode_cursor(movie, &sort_options.0));
+
+    let mut genres =
+        find_movie_genres(db, &movies.iter().map(|m| m.id).collect::<Vec<_>>()).await?;
+    let movies = movies
+        .into_iter()
+        .map(|movie| {
+            let movie_genres = genres.remove(&movie.id).unwrap_or_default();
+            map_movie(movie, movie_genres)
         })
         .collect();
 
-    Ok((
-        movies,
-        Info {
-            current_page: options.page,
-            // Somehow, if the current page is the last page,
-            // no movies found; so we decrement the total page by 1
-            total_page: total_page - 1,
-        },
-    ))
+    Ok((movies, info.with_next_cursor(next_cursor)))
 }
 
 pub async fn get_movie(db: &DatabaseConnection, movie_id: String) -> Result<Movie> {
@@ -73,17 +605,309 @@ pub async fn get_movie(db: &DatabaseConnection, movie_id: String) -> Result<Movi
     let movie = movie::Entity::find_by_id(movie_id).one(db).await?;
 
     match movie {
-        Some(movie) => Ok(Movie {
-            id: movie.id.to_string(),
-            title: movie.title,
-            overview: movie.overview,
-            rating: movie.rating,
-            genre: movie.genre,
-            poster_url: movie.poster_url,
-        }),
+        Some(movie) => {
+            let genres = find_movie_genres(db, &[movie.id]).await?;
+            let movie_genres = genres.get(&movie.id).cloned().unwrap_or_default();
+
+            Ok(map_movie(movie, movie_genres))
+        }
         None => Err(AppError::NotFound(format!(
             "Movie with id: {} does not exist",
-            movie_id.to_string()
+            movie_id
         ))),
     }
 }
+
+impl From<CreateMovieRequest> for MovieFields {
+    fn from(request: CreateMovieRequest) -> Self {
+        Self {
+            title: request.title,
+            overview: request.overview,
+            rating: request.rating,
+            genres: request.genres,
+            poster_url: request.poster_url,
+            runtime_minutes: request.runtime_minutes,
+            release_date: request.release_date,
+            age_rating: request.age_rating,
+            language: request.language,
+            trailer_url: request.trailer_url,
+            director: request.director,
+            cast: request.cast.unwrap_or_default(),
+        }
+    }
+}
+
+fn set_movie_fields(movie: &mut movie::ActiveModel, fields: MovieFields) {
+    movie.title = Set(fields.title);
+    movie.overview = Set(fields.overview);
+    movie.rating = Set(fields.rating);
+    movie.poster_url = Set(fields.poster_url);
+    movie.runtime_minutes = Set(fields.runtime_minutes);
+    movie.release_date = Set(fields.release_date);
+    movie.age_rating = Set(fields.age_rating);
+    movie.language = Set(fields.language);
+    movie.trailer_url = Set(fields.trailer_url);
+    movie.director = Set(fields.director);
+    movie.cast_members = Set(fields.cast);
+}
+
+// Replaces the genres the movie is filed under.
+async fn set_movie_genres<C: ConnectionTrait>(
+    db: &C,
+    movie_id: Uuid,
+    genres: &[genre::Model],
+) -> Result<()> {
+    movie_genre::Entity::delete_many()
+        .filter(movie_genre::Column::MovieId.eq(movie_id))
+        .exec(db)
+        .await?;
+
+    movie_genre::Entity::insert_many(genres.iter().map(|genre| movie_genre::ActiveModel {
+        movie_id: Set(movie_id),
+        genre_id: Set(genre.id),
+    }))
+    .exec(db)
+    .await?;
+
+    Ok(())
+}
+
+async fn find_movie<C: ConnectionTrait>(
+    db: &C,
+    movie_id: String,
+    lock: bool,
+) -> Result<movie::Model> {
+    let movie_id = Uuid::from_str(&movie_id)?;
+
+    let mut query = movie::Entity::find_by_id(movie_id);
+    if lock {
+        query = query.lock_exclusive();
+    }
+
+    query
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Movie with id: {} does not exist", movie_id)))
+}
+
+pub async fn create_movie(db: &DatabaseConnection, request: CreateMovieRequest) -> Result<Movie> {
+    let txn = db.begin().await?;
+    let known_genres = genre::Entity::find().all(&txn).await?;
+
+    let (fields, genres) = validate_movie_fields(request.into(), &known_genres)?;
+    let genre_names = fields.genres.to_owned();
+
+    let mut movie = movie::ActiveModel {
+        id: Set(Uuid::now_v7()),
+        ..Default::default()
+    };
+    set_movie_fields(&mut movie, fields);
+
+    let movie = movie.insert(&txn).await?;
+    set_movie_genres(&txn, movie.id, &genres).await?;
+
+    txn.commit().await?;
+
+    Ok(map_movie(movie, genre_names))
+}
+
+/// Replaces every field of the movie, so optional fields left out of the request are cleared.
+pub async fn replace_movie(
+    db: &DatabaseConnection,
+    movie_id: String,
+    request: CreateMovieRequest,
+) -> Result<Movie> {
+    let movie = find_movie(db, movie_id, false).await?;
+
+    let txn = db.begin().await?;
+    let known_genres = genre::Entity::find().all(&txn).await?;
+
+    let (fields, genres) = validate_movie_fields(request.into(), &known_genres)?;
+    let genre_names = fields.genres.to_owned();
+
+    let mut movie = movie.into_active_model();
+    set_movie_fields(&mut movie, fields);
+
+    let movie = movie.update(&txn).await?;
+    set_movie_genres(&txn, movie.id, &genres).await?;
+
+    txn.commit().await?;
+
+    Ok(map_movie(movie, genre_names))
+}
+
+pub async fn update_movie(
+    db: &DatabaseConnection,
+    movie_id: String,
+    request: UpdateMovieRequest,
+) -> Result<Movie> {
+    let movie = find_movie(db, movie_id, false).await?;
+
+    let txn = db.begin().await?;
+    let known_genres = genre::Entity::find().all(&txn).await?;
+
+    let genres = match request.genres {
+        Some(genres) => genres,
+        None => find_movie_genres(&txn, &[movie.id])
+            .await?
+            .remove(&movie.id)
+            .unwrap_or_default(),
+    };
+
+    let (fields, genres) = validate_movie_fields(
+        MovieFields {
+            title: request.title.unwrap_or(movie.title.to_owned()),
+            overview: request.overview.unwrap_or(movie.overview.to_owned()),
+            rating: request.rating.unwrap_or(movie.rating),
+            genres,
+            poster_url: request.poster_url.unwrap_or(movie.poster_url.to_owned()),
+            runtime_minutes: request.runtime_minutes.or(movie.runtime_minutes),
+            release_date: request.release_date.or(movie.release_date),
+            age_rating: request.age_rating.or(movie.age_rating.to_owned()),
+            language: request.language.or(movie.language.to_owned()),
+            trailer_url: request.trailer_url.or(movie.trailer_url.to_owned()),
+            director: request.director.or(movie.director.to_owned()),
+            cast: request.cast.unwrap_or(movie.cast_members.to_owned()),
+        },
+        &known_genres,
+    )?;
+    let genre_names = fields.genres.to_owned();
+
+    let mut movie = movie.into_active_model();
+    set_movie_fields(&mut movie, fields);
+
+    let movie = movie.update(&txn).await?;
+    set_movie_genres(&txn, movie.id, &genres).await?;
+
+    txn.commit().await?;
+
+    Ok(map_movie(movie, genre_names))
+}
+
+pub async fn delete_movie(db: &DatabaseConnection, movie_id: String) -> Result<()> {
+    let txn = db.begin().await?;
+
+    // Locked so that no showtime can be created for the movie between counting and deleting,
+    // which would otherwise be removed along with it
+    let movie = find_movie(&txn, movie_id, true).await?;
+
+    let showtime_count = showtime::Entity::find()
+        .filter(showtime::Column::MovieId.eq(movie.id))
+        .count(&txn)
+        .await?;
+
+    if showtime_count > 0 {
+        return Err(AppError::Conflict(format!(
+            "Movie with id: {} still has {} showtimes",
+            movie.id, showtime_count
+        )));
+    }
+
+    movie::Entity::delete_by_id(movie.id).exec(&txn).await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+// Builds a prefix `tsquery` so partially typed words still match, e.g. `inter stel` becomes
+// `inter:* & stel:*`. Returns `None` when the query has no searchable words.
+fn to_prefix_tsquery(query: &str) -> Option<String> {
+    let terms = query
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("{word}:*"))
+        .collect::<Vec<_>>();
+
+    (!terms.is_empty()).then(|| terms.join(" & "))
+}
+
+/// Searches titles and overviews, best matches first.
+pub async fn search_movies(
+    db: &DatabaseConnection,
+    params: SearchMoviesQueryParams,
+) -> Result<Vec<Movie>> {
+    let search = params.q.trim().to_string();
+
+    let mut errors = vec![];
+    if search.is_empty() {
+        errors.push(FieldError {
+            field: "q".to_string(),
+            message: "Search query must not be empty".to_string(),
+        });
+    }
+    if !(1..=MAX_SEARCH_LIMIT).contains(&params.limit) {
+        errors.push(FieldError {
+            field: "limit".to_string(),
+            message: format!("Limit must be between 1 and {MAX_SEARCH_LIMIT}"),
+        });
+    }
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let limit = params.limit as i64;
+
+    let mut movies = match to_prefix_tsquery(&search) {
+        Some(tsquery) => {
+            movie::Entity::find()
+                .from_raw_sql(raw_sql!(
+                    Postgres,
+                    r#"
+                    SELECT m.*
+                    FROM movie m,
+                         to_tsquery('english', {tsquery}) query
+                    WHERE m.search_vector @@ query
+                    ORDER BY ts_rank(m.search_vector, query) DESC, m.title
+                    LIMIT {limit}
+                    "#
+                ))
+                .all(db)
+                .await?
+        }
+        None => vec![],
+    };
+
+    if movies.is_empty() && search.chars().count() <= TRIGRAM_FALLBACK_MAX_LEN {
+        let txn = db.begin().await?;
+
+        // `<%` only uses the trigram index with the threshold set on the connection
+        let _ = txn
+            .execute_unprepared(&format!(
+                "SET LOCAL pg_trgm.word_similarity_threshold = {TRIGRAM_THRESHOLD}"
+            ))
+            .await?;
+
+        movies = movie::Entity::find()
+            .from_raw_sql(raw_sql!(
+                Postgres,
+                r#"
+                SELECT m.*
+                FROM movie m
+                WHERE {search} <% m.title
+                ORDER BY word_similarity({search}, m.title) DESC, m.title
+                LIMIT {limit}
+                "#
+            ))
+            .all(&txn)
+            .await?;
+
+        txn.commit().await?;
+    }
+
+    let mut genres =
+        find_movie_genres(db, &movies.iter().map(|m| m.id).collect::<Vec<_>>()).await?;
+
+    Ok(movies
+        .into_iter()
+        .map(|movie| {
+            let movie_genres = genres.remove(&movie.id).unwrap_or_default();
+            map_movie(movie, movie_genres)
+        })
+        .collect())
+}
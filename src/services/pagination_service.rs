@@ -0,0 +1,74 @@
+use serde::Serialize;
+
+use crate::app_error::FieldError;
+
+/// Most items a single page of any list can have.
+pub const MAX_PAGE_SIZE: u64 = 100;
+
+/// Checks the `limit` and 1-based `page` of a paginated list, recording what is wrong with them
+/// alongside the other errors of the request.
+pub fn validate_pagination(limit: u64, page: u64, errors: &mut Vec<FieldError>) {
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+        errors.push(FieldError {
+            field: "limit".to_string(),
+            message: format!("Limit must be between 1 and {MAX_PAGE_SIZE}"),
+        });
+    }
+    if page == 0 {
+        errors.push(FieldError {
+            field: "page".to_string(),
+            message: "Page must be 1 or greater".to_string(),
+        });
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Info {
+    /// 1-based, or `None` when paginating with a cursor
+    pub current_page: Option<u64>,
+    pub total_page: u64,
+    pub total_items: u64,
+    pub page_size: u64,
+    pub has_next: bool,
+    pub has_prev: bool,
+    /// Pass as `after` to fetch the items following this page, for lists that take a cursor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl Info {
+    pub fn new(page: u64, limit: u64, total_items: u64) -> Self {
+        let total_page = total_items.div_ceil(limit);
+
+        Self {
+            current_page: Some(page),
+            total_page,
+            total_items,
+            page_size: limit,
+            has_next: page < total_page,
+            has_prev: page > 1,
+            next_cursor: None,
+        }
+    }
+
+    /// A page fetched after a cursor, which has no page number.
+    pub fn after_cursor(limit: u64, total_items: u64, has_next: bool, has_prev: bool) -> Self {
+        Self {
+            current_page: None,
+            total_page: total_items.div_ceil(limit),
+            total_items,
+            page_size: limit,
+            has_next,
+            has_prev,
+            next_cursor: None,
+        }
+    }
+
+    pub fn with_next_cursor(self, next_cursor: Option<String>) -> Self {
+        Self {
+            next_cursor,
+            ..self
+        }
+    }
+}
@@ -0,0 +1,527 @@
+use chrono::Utc;
+use entity::{
+    booking, payment_event,
+    sea_orm_active_enums::{BookingStatus, RefundStatus},
+};
+use hmac::{Hmac, Mac};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, DatabaseTransaction,
+    EntityTrait, IntoActiveModel, QueryFilter, QuerySelect, TransactionTrait, TryInsertResult,
+    sea_query::OnConflict,
+};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::{
+    app_error::AppError, app_state::Result,
+    models::requests::payment_webhook_request_model::PaymentWebhookEvent, payments::PaymentGateway,
+};
+
+use super::{
+    booking_service::{lock_showtime_room, release_seats, sell_seats},
+    ticket_service::issue_tickets,
+};
+
+const CAPTURED_EVENT: &str = "payment.captured";
+const FAILED_EVENT: &str = "payment.failed";
+const REFUNDED_EVENT: &str = "payment.refunded";
+
+/// Checks that `signature` is the hex encoded HMAC-SHA256 of the payload.
+fn verify_signature(secret: &str, payload: &[u8], signature: &str) -> Result<()> {
+    let invalid_signature =
+        || AppError::Unauthorized("Payment webhook signature is invalid".to_string());
+
+    let signature = signature.trim();
+    let signature = hex::decode(signature.strip_prefix("sha256=").unwrap_or(signature))
+        .map_err(|_| invalid_signature())?;
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|err| AppError::from(anyhow::anyhow!(err)))?;
+    mac.update(payload);
+
+    mac.verify_slice(&signature)
+        .map_err(|_| invalid_signature())
+}
+
+/// Records the event, returning `false` when it was received before.
+async fn record_event(
+    txn: &DatabaseTransaction,
+    provider: &str,
+    event: &PaymentWebhookEvent,
+    booking_id: Uuid,
+) -> Result<bool> {
+    let inserted = payment_event::Entity::insert(payment_event::ActiveModel {
+        id: Set(event.id.to_owned()),
+        provider: Set(provider.to_string()),
+        event_type: Set(event.event_type.to_owned()),
+        payment_intent_id: Set(event.data.intent_id.to_owned()),
+        booking_id: Set(Some(booking_id)),
+        occurred_at: Set(event.created_at.naive_utc()),
+        ..Default::default()
+    })
+    .on_conflict(
+        OnConflict::columns([payment_event::Column::Provider, payment_event::Column::Id])
+            .do_nothing()
+            .to_owned(),
+    )
+    .do_nothing()
+    .exec(txn)
+    .await?;
+
+    Ok(matches!(inserted, TryInsertResult::Inserted(_)))
+}
+
+/// Moves the booking along for the event. Bookings only ever move forward, so events that
+/// arrive late or out of order leave them alone:
+///
+/// - a capture sells the seats of a booking that is still waiting for its payment, or refunds
+///   the payment when the seats were sold to someone else in the meantime. A capture for a
+///   booking that already failed is refunded rather than reviving it, unless its payment is
+///   already being paid back
+/// - a failure only applies to a booking that is still waiting for its payment, releasing any
+///   seats checkout already sold it
+/// - a refund cancels a confirmed booking and releases its seats, voiding their tickets
+///
+/// Returns whether the payment has to be refunded once the transaction is committed.
+async fn apply_event(
+    txn: &DatabaseTransaction,
+    event_type: &str,
+    booking: booking::Model,
+) -> Result<bool> {
+    let now = Utc::now().naive_utc();
+    let booking_id = booking.id;
+
+    match event_type {
+        CAPTURED_EVENT if booking.status == BookingStatus::PendingPayment => {
+            let sold_seats = sell_seats(txn, &booking).await?;
+            let is_sold = booking.seats.iter().all(|seat| sold_seats.contains(seat));
+
+            let mut booking = booking.into_active_model();
+            if is_sold {
+                issue_tickets(txn, booking_id).await?;
+                booking.status = Set(BookingStatus::Confirmed);
+            } else {
+                release_seats(txn, booking_id).await?;
+                booking.status = Set(BookingStatus::PaymentFailed);
+                booking.refund_status = Set(RefundStatus::Pending);
+            }
+            booking.updated_at = Set(now);
+            booking.update(txn).await?;
+
+            Ok(!is_sold)
+        }
+        CAPTURED_EVENT
+            if booking.status == BookingStatus::PaymentFailed
+                && booking.refund_status == RefundStatus::None =>
+        {
+            let mut booking = booking.into_active_model();
+            booking.refund_status = Set(RefundStatus::Pending);
+            booking.updated_at = Set(now);
+            booking.update(txn).await?;
+
+            Ok(true)
+        }
+        FAILED_EVENT if booking.status == BookingStatus::PendingPayment => {
+            // Checkout sells the seats before capturing the payment
+            release_seats(txn, booking_id).await?;
+
+            let mut booking = booking.into_active_model();
+            booking.status = Set(BookingStatus::PaymentFailed);
+            booking.updated_at = Set(now);
+            booking.update(txn).await?;
+
+            Ok(false)
+        }
+        REFUNDED_EVENT if booking.refund_status != RefundStatus::Refunded => {
+            let status = match booking.status {
+                BookingStatus::Confirmed => BookingStatus::Cancelled,
+                BookingStatus::PendingPayment => BookingStatus::PaymentFailed,
+                ref status => status.to_owned(),
+            };
+            if status != booking.status {
+                release_seats(txn, booking_id).await?;
+            }
+
+            let mut booking = booking.into_active_model();
+            if status == BookingStatus::Cancelled {
+                booking.cancelled_at = Set(Some(now));
+            }
+            booking.status = Set(status);
+            booking.refund_status = Set(RefundStatus::Refunded);
+            booking.updated_at = Set(now);
+            booking.update(txn).await?;
+
+            Ok(false)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Verifies and applies a payment webhook. Each event is applied once, deliveries of an event
+/// that was already received are acknowledged without doing anything, while events for an intent
+/// that is not linked to a booking yet are refused so that they are delivered again. Returns
+/// whether the event was new.
+pub async fn handle_payment_webhook(
+    db: &DatabaseConnection,
+    payments: &PaymentGateway,
+    secret: Option<&str>,
+    signature: Option<&str>,
+    payload: &[u8],
+) -> Result<bool> {
+    let secret = secret.filter(|secret| !secret.is_empty()).ok_or_else(|| {
+        AppError::PaymentUnavailable("Payment webhooks are not configured".to_string())
+    })?;
+    let signature = signature.ok_or_else(|| {
+        AppError::Unauthorized("Payment webhook signature is missing".to_string())
+    })?;
+    verify_signature(secret, payload, signature)?;
+
+    let event = serde_json::from_slice::<PaymentWebhookEvent>(payload)
+        .map_err(|err| AppError::BadRequest(format!("Payment webhook is malformed: {err}")))?;
+    let provider = payments.provider_name();
+
+    let txn = db.begin().await?;
+
+    let booking = booking::Entity::find()
+        .filter(booking::Column::PaymentProvider.eq(provider))
+        .filter(booking::Column::PaymentIntentId.eq(&event.data.intent_id))
+        .one(&txn)
+        .await?;
+
+    // Checkout links the intent to its booking only once the provider has created it, so an
+    // event can beat it here. The event is left unrecorded and refused for the provider to
+    // deliver it again.
+    let Some(booking) = booking else {
+        txn.rollback().await?;
+        log::warn!(
+            "Payment event {} is for unknown payment intent {}",
+            event.id,
+            event.data.intent_id
+        );
+        return Err(AppError::NotFound(format!(
+            "Payment intent {} does not belong to a booking yet",
+            event.data.intent_id
+        )));
+    };
+
+    if !record_event(&txn, provider, &event, booking.id).await? {
+        txn.rollback().await?;
+        return Ok(false);
+    }
+
+    // Same locking order as checkout, so the event waits for a booking that is being paid for
+    lock_showtime_room(&txn, booking.showtime_id, booking.showtime_room_id).await?;
+    let booking = booking::Entity::find_by_id(booking.id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("Booking with id: {} does not exist", booking.id))
+        })?;
+    let booking_id = booking.id;
+
+    let needs_refund = apply_event(&txn, &event.event_type, booking).await?;
+
+    txn.commit().await?;
+
+    // The payment was captured for seats that went to someone else or for a booking that already
+    // failed, so the customer is paid back
+    if needs_refund {
+        match payments.refund(&event.data.intent_id).await {
+            Ok(_) => {
+                booking::ActiveModel {
+                    id: Set(booking_id),
+                    refund_status: Set(RefundStatus::Refunded),
+                    ..Default::default()
+                }
+                .update(db)
+                .await?;
+            }
+            Err(err) => log::error!("Failed to refund booking {booking_id}: {err}"),
+        }
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use entity::{
+        booking,
+        sea_orm_active_enums::{BookingStatus, RefundStatus},
+        showtime_room, taken_seat,
+    };
+    use hmac::{Hmac, Mac};
+    use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, MockExecResult};
+    use serde_json::json;
+    use sha2::Sha256;
+    use std::{sync::Arc, time::Duration};
+    use uuid::Uuid;
+
+    use crate::{
+        app_error::AppError,
+        config::MockPaymentOutcome,
+        payments::{
+            CreatePaymentIntent, PaymentGateway, PaymentProvider, PaymentStatus,
+            mock_payment_provider::MockPaymentProvider,
+        },
+    };
+
+    use super::{handle_payment_webhook, verify_signature};
+
+    const SECRET: &str = "payment-webhook-secret-for-the-tests";
+    const PAYLOAD: &[u8] =
+        br#"{"id":"evt_1","type":"payment.captured","data":{"intentId":"pi_1"}}"#;
+
+    fn sign(secret: &str, payload: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(payload);
+
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn signed_payload_verifies() {
+        let signature = sign(SECRET, PAYLOAD);
+
+        assert!(verify_signature(SECRET, PAYLOAD, &signature).is_ok());
+        assert!(verify_signature(SECRET, PAYLOAD, &format!("sha256={signature}")).is_ok());
+    }
+
+    #[test]
+    fn tampered_payload_is_rejected() {
+        let signature = sign(SECRET, PAYLOAD);
+        let tampered_payload =
+            br#"{"id":"evt_1","type":"payment.refunded","data":{"intentId":"pi_1"}}"#;
+
+        let result = verify_signature(SECRET, tampered_payload, &signature);
+
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn payload_signed_with_another_key_is_rejected() {
+        let signature = sign("another-payment-webhook-secret", PAYLOAD);
+
+        let result = verify_signature(SECRET, PAYLOAD, &signature);
+
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    const EXEC_RESULT: MockExecResult = MockExecResult {
+        last_insert_id: 0,
+        rows_affected: 1,
+    };
+
+    struct Webhook {
+        booking: booking::Model,
+        showtime_room: showtime_room::Model,
+        taken_seat: taken_seat::Model,
+        provider: Arc<MockPaymentProvider>,
+    }
+
+    impl Webhook {
+        async fn new() -> Self {
+            let now = Utc::now();
+            let booking_id = Uuid::now_v7();
+            let showtime_id = Uuid::now_v7();
+            let provider = Arc::new(MockPaymentProvider::new(MockPaymentOutcome::Succeed));
+
+            let intent = provider
+                .create_intent(CreatePaymentIntent {
+                    booking_id,
+                    amount: 50_000,
+                    payment_method: None,
+                })
+                .await
+                .unwrap();
+
+            Self {
+                booking: booking::Model {
+                    id: booking_id,
+                    showtime_id,
+                    showtime_room_id: 1,
+                    user_id: Some(Uuid::now_v7()),
+                    seats: vec!["A1".to_string()],
+                    amount: 50_000,
+                    status: BookingStatus::PendingPayment,
+                    refund_status: RefundStatus::None,
+                    cancellation_reason: None,
+                    cancelled_at: None,
+                    created_at: now.naive_utc(),
+                    updated_at: now.naive_utc(),
+                    payment_provider: Some("mock".to_string()),
+                    payment_intent_id: Some(intent.id),
+                },
+                showtime_room: showtime_room::Model {
+                    id: 1,
+                    time: now.fixed_offset(),
+                    price: 50_000,
+                    room_id: Uuid::now_v7(),
+                    showtime_id,
+                },
+                taken_seat: taken_seat::Model {
+                    id: 1,
+                    showtime_id,
+                    showtime_room_id: 1,
+                    seat_identifier: "A1".to_string(),
+                    booking_id: Some(booking_id),
+                },
+                provider,
+            }
+        }
+
+        fn intent_id(&self) -> &str {
+            self.booking.payment_intent_id.as_deref().unwrap()
+        }
+
+        fn with_status(
+            &self,
+            status: BookingStatus,
+            refund_status: RefundStatus,
+        ) -> booking::Model {
+            booking::Model {
+                status,
+                refund_status,
+                ..self.booking.clone()
+            }
+        }
+
+        /// Finding, recording and locking the booking of a new event.
+        fn receive(&self, db: MockDatabase, booking: booking::Model) -> MockDatabase {
+            db.append_query_results([vec![booking.clone()]])
+                .append_exec_results([EXEC_RESULT])
+                .append_query_results([vec![self.showtime_room.clone()]])
+                .append_query_results([vec![booking]])
+        }
+
+        async fn deliver(
+            &self,
+            db: &DatabaseConnection,
+            event_id: &str,
+            event_type: &str,
+        ) -> crate::app_state::Result<bool> {
+            let payload = serde_json::to_vec(&json!({
+                "id": event_id,
+                "type": event_type,
+                "createdAt": Utc::now(),
+                "data": { "intentId": self.intent_id() },
+            }))
+            .unwrap();
+            let payments =
+                PaymentGateway::with_provider(self.provider.clone(), Duration::from_millis(50));
+
+            handle_payment_webhook(
+                db,
+                &payments,
+                Some(SECRET),
+                Some(&sign(SECRET, &payload)),
+                &payload,
+            )
+            .await
+        }
+    }
+
+    fn transaction_log(db: DatabaseConnection) -> String {
+        format!("{:?}", db.into_transaction_log())
+    }
+
+    #[actix_web::test]
+    async fn capture_confirms_a_booking_awaiting_its_payment() {
+        let webhook = Webhook::new().await;
+        let pending = webhook.booking.clone();
+        let db = webhook
+            .receive(MockDatabase::new(DatabaseBackend::Postgres), pending)
+            .append_exec_results([EXEC_RESULT, EXEC_RESULT])
+            .append_query_results([
+                vec![webhook.taken_seat.clone()],
+                vec![webhook.taken_seat.clone()],
+            ])
+            .append_query_results([vec![
+                webhook.with_status(BookingStatus::Confirmed, RefundStatus::None),
+            ]])
+            .into_connection();
+
+        let result = webhook.deliver(&db, "evt_1", "payment.captured").await;
+
+        assert!(result.unwrap());
+        assert_eq!(
+            webhook.provider.intent_status(webhook.intent_id()),
+            Some(PaymentStatus::RequiresCapture)
+        );
+        let log = transaction_log(db);
+        assert!(log.contains(r#"INSERT INTO \"ticket\""#));
+        assert!(log.contains("confirmed"));
+    }
+
+    #[actix_web::test]
+    async fn replayed_event_is_acknowledged_without_applying_it() {
+        let webhook = Webhook::new().await;
+        let confirmed = webhook.with_status(BookingStatus::Confirmed, RefundStatus::None);
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![confirmed]])
+            .append_exec_results([MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 0,
+            }])
+            .into_connection();
+
+        let result = webhook.deliver(&db, "evt_1", "payment.captured").await;
+
+        assert!(!result.unwrap());
+        assert!(!transaction_log(db).contains("UPDATE"));
+    }
+
+    #[actix_web::test]
+    async fn capture_after_the_booking_failed_refunds_the_payment() {
+        let webhook = Webhook::new().await;
+        webhook.provider.capture(webhook.intent_id()).await.unwrap();
+        let failed = webhook.with_status(BookingStatus::PaymentFailed, RefundStatus::None);
+        let db = webhook
+            .receive(MockDatabase::new(DatabaseBackend::Postgres), failed)
+            .append_query_results([
+                vec![webhook.with_status(BookingStatus::PaymentFailed, RefundStatus::Pending)],
+                vec![webhook.with_status(BookingStatus::PaymentFailed, RefundStatus::Refunded)],
+            ])
+            .into_connection();
+
+        let result = webhook.deliver(&db, "evt_1", "payment.captured").await;
+
+        assert!(result.unwrap());
+        assert_eq!(
+            webhook.provider.intent_status(webhook.intent_id()),
+            Some(PaymentStatus::Refunded)
+        );
+        let log = transaction_log(db);
+        assert!(!log.contains(r#"INSERT INTO \"taken_seat\""#));
+        assert!(log.contains("pending"));
+        assert!(log.contains("refunded"));
+    }
+
+    #[actix_web::test]
+    async fn capture_after_a_refund_leaves_the_booking_alone() {
+        let webhook = Webhook::new().await;
+        let pending = webhook.booking.clone();
+        let refunded = webhook.with_status(BookingStatus::PaymentFailed, RefundStatus::Refunded);
+        let db = webhook.receive(MockDatabase::new(DatabaseBackend::Postgres), pending);
+        let db = db
+            .append_exec_results([EXEC_RESULT, EXEC_RESULT])
+            .append_query_results([vec![refunded.clone()]]);
+        let db = webhook.receive(db, refunded).into_connection();
+
+        let refund = webhook.deliver(&db, "evt_1", "payment.refunded").await;
+        let capture = webhook.deliver(&db, "evt_2", "payment.captured").await;
+
+        assert!(refund.unwrap());
+        assert!(capture.unwrap());
+        assert_eq!(
+            webhook.provider.intent_status(webhook.intent_id()),
+            Some(PaymentStatus::RequiresCapture)
+        );
+        let log = transaction_log(db);
+        assert!(log.contains("payment_failed"));
+        assert!(!log.contains(r#"INSERT INTO \"taken_seat\""#));
+        assert_eq!(log.matches("UPDATE \\\"booking\\\"").count(), 1);
+    }
+}
@@ -0,0 +1,377 @@
+use anyhow::anyhow;
+use chrono::Utc;
+use entity::{room, showtime_room, taken_seat};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, ConnectionTrait,
+    DatabaseConnection, EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, TransactionTrait,
+};
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::{
+    app_error::{AppError, FieldError},
+    app_state::Result,
+    extractors::authenticated_user::AuthenticatedUser,
+    models::{
+        requests::{
+            create_room_request_model::CreateRoomRequest,
+            update_room_request_model::UpdateRoomRequest,
+        },
+        room_layout_model::RoomLayout,
+        room_model::Room,
+    },
+};
+
+use super::{
+    seat_map_service::{bookable_seats, seat_capacity},
+    theaters_service::find_theater,
+};
+
+fn map_room(room: room::Model) -> Result<Room> {
+    let layout = room
+        .layout
+        .map(serde_json::from_value::<RoomLayout>)
+        .transpose()
+        .map_err(|e| anyhow!("Room with id: {} has a malformed layout: {e}", room.id))?;
+
+    Ok(Room {
+        id: room.id.to_string(),
+        theater_id: room.theater_id.to_string(),
+        name: room.name,
+        capacity: room.capacity,
+        max_rows: room.max_rows,
+        max_columns: room.max_columns,
+        layout,
+    })
+}
+
+struct RoomFields {
+    name: String,
+    capacity: i32,
+    max_rows: Option<i32>,
+    max_columns: Option<i32>,
+    layout: Option<RoomLayout>,
+}
+
+// Builds the room described by `fields`, checking that its dimensions agree with the layout
+// and that the capacity fits in the grid.
+fn build_room(id: Uuid, theater_id: Uuid, fields: RoomFields) -> Result<room::Model> {
+    let mut errors = vec![];
+
+    let name = fields.name.trim().to_string();
+    if name.is_empty() {
+        errors.push(FieldError {
+            field: "name".to_string(),
+            message: "Name must not be empty".to_string(),
+        });
+    }
+
+    let mut layout_size = None;
+    if let Some(layout) = &fields.layout {
+        match layout.grid() {
+            Ok(grid) if !grid.iter().any(|row| row.iter().any(|kind| kind.is_seat())) => {
+                errors.push(FieldError {
+                    field: "layout".to_string(),
+                    message: "Layout must contain at least one seat".to_string(),
+                });
+            }
+            Ok(grid) => {
+                let max_columns = grid.iter().map(Vec::len).max().unwrap_or_default();
+                layout_size = Some((grid.len() as i32, max_columns as i32));
+            }
+            Err(message) => errors.push(FieldError {
+                field: "layout".to_string(),
+                message,
+            }),
+        }
+    }
+
+    let dimensions = [
+        ("maxRows", fields.max_rows, layout_size.map(|size| size.0)),
+        (
+            "maxColumns",
+            fields.max_columns,
+            layout_size.map(|size| size.1),
+        ),
+    ]
+    .map(|(field, value, from_layout)| match (value, from_layout) {
+        (Some(value), Some(from_layout)) if value != from_layout => {
+            errors.push(FieldError {
+                field: field.to_string(),
+                message: format!("{field} must be {from_layout} to match the layout"),
+            });
+            0
+        }
+        (_, Some(from_layout)) => from_layout,
+        (Some(value), None) if value > 0 => value,
+        (Some(_), None) => {
+            errors.push(FieldError {
+                field: field.to_string(),
+                message: format!("{field} must be greater than 0"),
+            });
+            0
+        }
+        (None, None) => {
+            // An invalid layout is reported on its own
+            if fields.layout.is_none() {
+                errors.push(FieldError {
+                    field: field.to_string(),
+                    message: format!("{field} is required when the room has no layout"),
+                });
+            }
+            0
+        }
+    });
+
+    let layout = fields
+        .layout
+        .map(|layout| serde_json::to_value(layout).map_err(|e| anyhow!(e)))
+        .transpose()?;
+
+    let room = room::Model {
+        id,
+        name,
+        capacity: fields.capacity,
+        max_rows: dimensions[0],
+        max_columns: dimensions[1],
+        theater_id,
+        layout,
+    };
+
+    if errors.is_empty() {
+        let seat_capacity = seat_capacity(&room)?;
+
+        if room.capacity <= 0 || room.capacity as usize > seat_capacity {
+            errors.push(FieldError {
+                field: "capacity".to_string(),
+                message: format!(
+                    "Capacity must be between 1 and the {seat_capacity} seats of the room"
+                ),
+            });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(room)
+    } else {
+        Err(AppError::Validation(errors))
+    }
+}
+
+async fn find_room_in_theater<C: ConnectionTrait>(
+    db: &C,
+    theater_id: Uuid,
+    room_id: Uuid,
+    lock: bool,
+) -> Result<room::Model> {
+    let mut query =
+        room::Entity::find_by_id(room_id).filter(room::Column::TheaterId.eq(theater_id));
+    if lock {
+        query = query.lock_exclusive();
+    }
+
+    query.one(db).await?.ok_or_else(|| {
+        AppError::NotFound(format!(
+            "Room with id: {} does not exist in theater: {}",
+            room_id, theater_id
+        ))
+    })
+}
+
+pub async fn get_rooms(db: &DatabaseConnection, theater_id: String) -> Result<Vec<Room>> {
+    let theater_id = Uuid::from_str(&theater_id)?;
+    let theater = find_theater(db, theater_id, false).await?;
+
+    room::Entity::find()
+        .filter(room::Column::TheaterId.eq(theater.id))
+        .order_by_asc(room::Column::Name)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(map_room)
+        .collect()
+}
+
+pub async fn get_room(
+    db: &DatabaseConnection,
+    theater_id: String,
+    room_id: String,
+) -> Result<Room> {
+    let theater_id = Uuid::from_str(&theater_id)?;
+    let room_id = Uuid::from_str(&room_id)?;
+
+    let room = find_room_in_theater(db, theater_id, room_id, false).await?;
+
+    map_room(room)
+}
+
+pub async fn create_room(
+    db: &DatabaseConnection,
+    actor: &AuthenticatedUser,
+    theater_id: String,
+    request: CreateRoomRequest,
+) -> Result<Room> {
+    let theater_id = Uuid::from_str(&theater_id)?;
+    actor.require_theater_manager(theater_id)?;
+
+    let theater = find_theater(db, theater_id, false).await?;
+
+    let room = build_room(
+        Uuid::now_v7(),
+        theater.id,
+        RoomFields {
+            name: request.name,
+            capacity: request.capacity,
+            max_rows: request.max_rows,
+            max_columns: request.max_columns,
+            layout: request.layout,
+        },
+    )?;
+
+    let room = room::ActiveModel {
+        id: Set(room.id),
+        name: Set(room.name),
+        capacity: Set(room.capacity),
+        max_rows: Set(room.max_rows),
+        max_columns: Set(room.max_columns),
+        theater_id: Set(room.theater_id),
+        layout: Set(room.layout),
+    }
+    .insert(db)
+    .await?;
+
+    map_room(room)
+}
+
+/// Updates the room unless that would take away seats that were already sold for one of its
+/// upcoming showtimes.
+pub async fn update_room(
+    db: &DatabaseConnection,
+    actor: &AuthenticatedUser,
+    theater_id: String,
+    room_id: String,
+    request: UpdateRoomRequest,
+) -> Result<Room> {
+    let theater_id = Uuid::from_str(&theater_id)?;
+    let room_id = Uuid::from_str(&room_id)?;
+    actor.require_theater_manager(theater_id)?;
+
+    let txn = db.begin().await?;
+
+    let current = find_room_in_theater(&txn, theater_id, room_id, true).await?;
+
+    let current_layout = current
+        .layout
+        .clone()
+        .map(serde_json::from_value::<RoomLayout>)
+        .transpose()
+        .map_err(|e| anyhow!("Room with id: {} has a malformed layout: {e}", current.id))?;
+
+    // Without a new layout the current one decides the dimensions, unless the caller changes them
+    let layout = match (request.layout, request.max_rows.or(request.max_columns)) {
+        (Some(layout), _) => Some(layout),
+        (None, Some(_)) => None,
+        (None, None) => current_layout,
+    };
+
+    let room = build_room(
+        current.id,
+        current.theater_id,
+        RoomFields {
+            name: request.name.unwrap_or(current.name.to_owned()),
+            capacity: request.capacity.unwrap_or(current.capacity),
+            max_rows: request
+                .max_rows
+                .or(layout.is_none().then_some(current.max_rows)),
+            max_columns: request
+                .max_columns
+                .or(layout.is_none().then_some(current.max_columns)),
+            layout,
+        },
+    )?;
+
+    // Locking the upcoming slots keeps bookings out until the new room is in place
+    let upcoming_showtime_room_ids = showtime_room::Entity::find()
+        .filter(
+            Condition::all()
+                .add(showtime_room::Column::RoomId.eq(room.id))
+                .add(showtime_room::Column::Time.gt(Utc::now())),
+        )
+        .lock_exclusive()
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|sr| sr.id)
+        .collect::<Vec<_>>();
+
+    let bookable_seats = bookable_seats(&room)?;
+    let mut lost_seats = taken_seat::Entity::find()
+        .filter(taken_seat::Column::ShowtimeRoomId.is_in(upcoming_showtime_room_ids))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|ts| ts.seat_identifier)
+        .filter(|seat| !bookable_seats.contains(seat))
+        .collect::<Vec<_>>();
+
+    if !lost_seats.is_empty() {
+        lost_seats.sort();
+        lost_seats.dedup();
+
+        return Err(AppError::Conflict(format!(
+            "Seats {} are sold for upcoming showtimes and cannot be removed",
+            lost_seats.join(", ")
+        )));
+    }
+
+    let mut active_room = current.into_active_model();
+    active_room.name = Set(room.name);
+    active_room.capacity = Set(room.capacity);
+    active_room.max_rows = Set(room.max_rows);
+    active_room.max_columns = Set(room.max_columns);
+    active_room.layout = Set(room.layout);
+
+    let room = active_room.update(&txn).await?;
+
+    txn.commit().await?;
+
+    map_room(room)
+}
+
+/// Deleting a room would cascade to its showtime slots and their bookings, so it is only
+/// allowed while the room has never been scheduled.
+pub async fn delete_room(
+    db: &DatabaseConnection,
+    actor: &AuthenticatedUser,
+    theater_id: String,
+    room_id: String,
+) -> Result<()> {
+    let theater_id = Uuid::from_str(&theater_id)?;
+    let room_id = Uuid::from_str(&room_id)?;
+    actor.require_theater_manager(theater_id)?;
+
+    let txn = db.begin().await?;
+
+    // Locked so that no slot can be scheduled in the room between counting and deleting, which
+    // would otherwise be removed along with it
+    let room = find_room_in_theater(&txn, theater_id, room_id, true).await?;
+
+    let showtime_room_count = showtime_room::Entity::find()
+        .filter(showtime_room::Column::RoomId.eq(room.id))
+        .count(&txn)
+        .await?;
+
+    if showtime_room_count > 0 {
+        return Err(AppError::Conflict(format!(
+            "Room with id: {} still has {} showtime slots",
+            room_id, showtime_room_count
+        )));
+    }
+
+    room::Entity::delete_by_id(room.id).exec(&txn).await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
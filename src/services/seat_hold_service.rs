@@ -0,0 +1,234 @@
+use chrono::Utc;
+use entity::{seat_hold, taken_seat, user};
+use sea_orm::{
+    ActiveValue::Set, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QuerySelect, TransactionTrait, TryInsertResult,
+    sea_query::OnConflict,
+};
+use std::{str::FromStr, time::Duration};
+use uuid::Uuid;
+
+use crate::{
+    app_error::AppError,
+    app_state::Result,
+    models::{
+        requests::create_seat_hold_request_model::CreateSeatHoldRequest, seat_hold_model::SeatHold,
+    },
+};
+
+use super::{
+    booking_service::{lock_showtime_room, parse_seats},
+    seat_map_service::{find_room, validate_seats_in_room},
+};
+
+/// Returns the seats among `seats` that are held by someone else, ignoring expired holds. Of the
+/// user's own holds only `hold_id` is ignored when one is given, and all of them otherwise.
+pub async fn find_held_seats<C: ConnectionTrait>(
+    db: &C,
+    showtime_room_id: i32,
+    seats: &[String],
+    user_id: Uuid,
+    hold_id: Option<Uuid>,
+) -> Result<Vec<String>> {
+    let mut condition = Condition::all()
+        .add(seat_hold::Column::ShowtimeRoomId.eq(showtime_room_id))
+        .add(seat_hold::Column::SeatIdentifier.is_in(seats))
+        .add(seat_hold::Column::ExpiresAt.gt(Utc::now().naive_utc()));
+
+    condition = match hold_id {
+        Some(hold_id) => condition.add(
+            Condition::any()
+                .add(seat_hold::Column::HoldId.ne(hold_id))
+                .add(seat_hold::Column::UserId.ne(user_id)),
+        ),
+        None => condition.add(seat_hold::Column::UserId.ne(user_id)),
+    };
+
+    let held_seats = seat_hold::Entity::find()
+        .filter(condition)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|sh| sh.seat_identifier)
+        .collect();
+
+    Ok(held_seats)
+}
+
+pub async fn release_expired_seat_holds<C: ConnectionTrait>(
+    db: &C,
+    showtime_room_id: Option<i32>,
+) -> Result<u64> {
+    let mut condition =
+        Condition::all().add(seat_hold::Column::ExpiresAt.lte(Utc::now().naive_utc()));
+
+    if let Some(showtime_room_id) = showtime_room_id {
+        condition = condition.add(seat_hold::Column::ShowtimeRoomId.eq(showtime_room_id));
+    }
+
+    let result = seat_hold::Entity::delete_many()
+        .filter(condition)
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected)
+}
+
+pub fn spawn_seat_hold_reaper(db: DatabaseConnection, every: Duration) {
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(every);
+
+        loop {
+            interval.tick().await;
+
+            match release_expired_seat_holds(&db, None).await {
+                Ok(0) => {}
+                Ok(released) => log::info!("Released {released} expired seat holds"),
+                Err(err) => err.log_error(),
+            }
+        }
+    });
+}
+
+/// Holds the seats for the user until `ttl_seconds` from now. A user holds at most
+/// `max_seats_per_user` seats at once, so no one can block a room by holding it bit by bit.
+pub async fn create_seat_hold(
+    db: &DatabaseConnection,
+    ttl_seconds: u64,
+    max_seats_per_user: u64,
+    user_id: Uuid,
+    showtime_id: String,
+    showtime_room_id: i32,
+    request: CreateSeatHoldRequest,
+) -> Result<SeatHold> {
+    let showtime_id = Uuid::from_str(&showtime_id)?;
+    let seats = parse_seats(&request.seats)?;
+
+    let hold_id = Uuid::now_v7();
+    let expires_at = Utc::now().naive_utc() + chrono::Duration::seconds(ttl_seconds as i64);
+
+    let txn = db.begin().await?;
+
+    let showtime_room = lock_showtime_room(&txn, showtime_id, showtime_room_id).await?;
+    let room = find_room(&txn, &showtime_room).await?;
+    validate_seats_in_room(&room, &seats)?;
+
+    // Locked so that holds the user places in other rooms at the same time are counted too
+    user::Entity::find_by_id(user_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::Unauthorized("User no longer exists".to_string()))?;
+
+    let held_seat_count = seat_hold::Entity::find()
+        .filter(seat_hold::Column::UserId.eq(user_id))
+        .filter(seat_hold::Column::ExpiresAt.gt(Utc::now().naive_utc()))
+        .count(&txn)
+        .await?;
+    if held_seat_count + seats.len() as u64 > max_seats_per_user {
+        return Err(AppError::Conflict(format!(
+            "You can hold at most {max_seats_per_user} seats at once and already hold \
+             {held_seat_count}, release a hold before holding more seats"
+        )));
+    }
+
+    // Expired holds that the reaper has not picked up yet would otherwise trip the unique index.
+    release_expired_seat_holds(&txn, Some(showtime_room_id)).await?;
+
+    let sold_seats = taken_seat::Entity::find()
+        .filter(
+            Condition::all()
+                .add(taken_seat::Column::ShowtimeId.eq(showtime_id))
+                .add(taken_seat::Column::ShowtimeRoomId.eq(showtime_room_id))
+                .add(taken_seat::Column::SeatIdentifier.is_in(&seats)),
+        )
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|ts| ts.seat_identifier)
+        .collect::<Vec<_>>();
+
+    let inserted =
+        seat_hold::Entity::insert_many(seats.iter().map(|seat| seat_hold::ActiveModel {
+            hold_id: Set(hold_id),
+            showtime_id: Set(showtime_id),
+            showtime_room_id: Set(showtime_room_id),
+            seat_identifier: Set(seat.to_owned()),
+            expires_at: Set(expires_at),
+            user_id: Set(user_id),
+            ..Default::default()
+        }))
+        .on_conflict(
+            OnConflict::columns([
+                seat_hold::Column::ShowtimeId,
+                seat_hold::Column::ShowtimeRoomId,
+                seat_hold::Column::SeatIdentifier,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .do_nothing()
+        .exec_with_returning_many(&txn)
+        .await?;
+
+    let held_seats = match inserted {
+        TryInsertResult::Inserted(rows) => rows
+            .into_iter()
+            .map(|row| row.seat_identifier)
+            .collect::<Vec<_>>(),
+        TryInsertResult::Empty | TryInsertResult::Conflicted => vec![],
+    };
+
+    let taken_seats = seats
+        .iter()
+        .filter(|seat| sold_seats.contains(seat) || !held_seats.contains(seat))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if !taken_seats.is_empty() {
+        txn.rollback().await?;
+        return Err(AppError::SeatsTaken(taken_seats));
+    }
+
+    txn.commit().await?;
+
+    Ok(SeatHold {
+        hold_id: hold_id.to_string(),
+        showtime_id: showtime_id.to_string(),
+        showtime_room_id,
+        seats,
+        expires_at,
+    })
+}
+
+/// Releases a hold of the user. Holds of other users are reported as missing.
+pub async fn release_seat_hold(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    showtime_id: String,
+    showtime_room_id: i32,
+    hold_id: String,
+) -> Result<()> {
+    let showtime_id = Uuid::from_str(&showtime_id)?;
+    let hold_id = Uuid::from_str(&hold_id)?;
+
+    let result = seat_hold::Entity::delete_many()
+        .filter(
+            Condition::all()
+                .add(seat_hold::Column::HoldId.eq(hold_id))
+                .add(seat_hold::Column::UserId.eq(user_id))
+                .add(seat_hold::Column::ShowtimeId.eq(showtime_id))
+                .add(seat_hold::Column::ShowtimeRoomId.eq(showtime_room_id)),
+        )
+        .exec(db)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound(format!(
+            "Seat hold with id: {} does not exist",
+            hold_id
+        )));
+    }
+
+    Ok(())
+}
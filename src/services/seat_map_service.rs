@@ -0,0 +1,236 @@
+use anyhow::anyhow;
+use entity::{room, showtime_room};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryFilter,
+};
+use std::{collections::HashSet, str::FromStr};
+use uuid::Uuid;
+
+use crate::{
+    app_error::{AppError, FieldError},
+    app_state::Result,
+    models::{
+        room_layout_model::RoomLayout,
+        seat_map_model::{Seat, SeatKind, SeatMap, SeatStatus},
+    },
+};
+
+use super::showtime_service::get_taken_seats;
+
+// `taken_seat.seat_identifier` is a varchar(3)
+const MAX_SEAT_IDENTIFIER_LEN: usize = 3;
+
+struct GridSeat {
+    id: String,
+    row: String,
+    column: u32,
+    kind: SeatKind,
+    bookable: bool,
+}
+
+/// Rows are labelled like spreadsheet columns: A to Z, then AA, AB and so on.
+pub fn row_label(index: u32) -> String {
+    let mut index = index;
+    let mut label = vec![];
+
+    loop {
+        label.push((b'A' + (index % 26) as u8) as char);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+
+    label.iter().rev().collect()
+}
+
+/// The kind of every grid position of the room, taken from its layout when it has one and
+/// otherwise a full `max_rows * max_columns` rectangle of seats.
+pub fn room_positions(room: &room::Model) -> Result<Vec<Vec<SeatKind>>> {
+    match &room.layout {
+        Some(layout) => {
+            let layout = serde_json::from_value::<RoomLayout>(layout.to_owned())
+                .map_err(|e| anyhow!("Room with id: {} has a malformed layout: {e}", room.id))?;
+
+            let positions = layout
+                .grid()
+                .map_err(|e| anyhow!("Room with id: {} has an invalid layout: {e}", room.id))?;
+
+            Ok(positions)
+        }
+        None => Ok((0..room.max_rows.max(0))
+            .map(|_| vec![SeatKind::Seat; room.max_columns.max(0) as usize])
+            .collect()),
+    }
+}
+
+// Seats are numbered by their column in the grid, so aisles and gaps leave holes in the
+// numbering. Once `capacity` seats have been placed the remaining ones are blocked.
+fn room_grid(room: &room::Model) -> Result<Vec<Vec<GridSeat>>> {
+    let mut remaining_capacity = room.capacity.max(0);
+
+    let grid = room_positions(room)?
+        .into_iter()
+        .enumerate()
+        .map(|(row_index, positions)| {
+            let row = row_label(row_index as u32);
+
+            positions
+                .into_iter()
+                .zip(1..)
+                .map(|(kind, column)| {
+                    let id = format!("{row}{column}");
+                    let bookable = kind.is_seat()
+                        && id.len() <= MAX_SEAT_IDENTIFIER_LEN
+                        && remaining_capacity > 0;
+
+                    if bookable {
+                        remaining_capacity -= 1;
+                    }
+
+                    GridSeat {
+                        id,
+                        row: row.clone(),
+                        column,
+                        kind,
+                        bookable,
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(grid)
+}
+
+pub async fn find_room<C: ConnectionTrait>(
+    db: &C,
+    showtime_room: &showtime_room::Model,
+) -> Result<room::Model> {
+    let room = showtime_room
+        .find_related(room::Entity)
+        .one(db)
+        .await?
+        .ok_or_else(|| {
+            anyhow!(
+                "Room with id: {} of showtime room: {} is missing",
+                showtime_room.room_id,
+                showtime_room.id
+            )
+        })?;
+
+    Ok(room)
+}
+
+/// The identifiers of every seat in the room that can be sold.
+pub fn bookable_seats(room: &room::Model) -> Result<HashSet<String>> {
+    let seats = room_grid(room)?
+        .into_iter()
+        .flatten()
+        .filter(|seat| seat.bookable)
+        .map(|seat| seat.id)
+        .collect();
+
+    Ok(seats)
+}
+
+/// The number of seats the grid of the room can hold, which is the most its capacity can be.
+pub fn seat_capacity(room: &room::Model) -> Result<usize> {
+    let unlimited = room::Model {
+        capacity: i32::MAX,
+        ..room.clone()
+    };
+
+    Ok(bookable_seats(&unlimited)?.len())
+}
+
+pub fn validate_seats_in_room(room: &room::Model, seats: &[String]) -> Result<()> {
+    let bookable_seats = bookable_seats(room)?;
+
+    let errors = seats
+        .iter()
+        .enumerate()
+        .filter(|(_, seat)| !bookable_seats.contains(*seat))
+        .map(|(index, seat)| FieldError {
+            field: format!("seats[{index}]"),
+            message: format!("{seat} is not a bookable seat in room {}", room.name),
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Validation(errors))
+    }
+}
+
+pub async fn get_seat_map(
+    db: &DatabaseConnection,
+    showtime_id: String,
+    showtime_room_id: i32,
+) -> Result<SeatMap> {
+    let showtime_uuid = Uuid::from_str(&showtime_id)?;
+
+    let showtime_room = showtime_room::Entity::find_by_id(showtime_room_id)
+        .filter(showtime_room::Column::ShowtimeId.eq(showtime_uuid))
+        .one(db)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!(
+                "Showtime room with id: {} does not exist for showtime: {}",
+                showtime_room_id, showtime_uuid
+            ))
+        })?;
+
+    let room = find_room(db, &showtime_room).await?;
+
+    let taken_seats = get_taken_seats(db, showtime_id, showtime_room_id).await?;
+
+    let rows = room_grid(&room)?
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|seat| {
+                    if !seat.kind.is_seat() {
+                        return Seat {
+                            id: None,
+                            row: seat.row,
+                            column: seat.column,
+                            kind: seat.kind,
+                            status: None,
+                        };
+                    }
+
+                    let status = if !seat.bookable {
+                        SeatStatus::Blocked
+                    } else if taken_seats.sold.contains(&seat.id) {
+                        SeatStatus::Sold
+                    } else if taken_seats.held.contains(&seat.id) {
+                        SeatStatus::Held
+                    } else {
+                        SeatStatus::Available
+                    };
+
+                    Seat {
+                        id: Some(seat.id),
+                        row: seat.row,
+                        column: seat.column,
+                        kind: seat.kind,
+                        status: Some(status),
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(SeatMap {
+        showtime_id: showtime_uuid.to_string(),
+        showtime_room_id: showtime_room.id,
+        room_id: room.id.to_string(),
+        room_name: room.name,
+        capacity: room.capacity,
+        max_rows: room.max_rows,
+        max_columns: room.max_columns,
+        rows,
+    })
+}
@@ -1,188 +1,357 @@
-use anyhow::{Context, anyhow};
-use chrono::NaiveDateTime;
-use entity::{showtime, taken_seat};
-use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, raw_sql};
-use serde_json::Value;
+use anyhow::anyhow;
+use chrono::{DateTime, DurationRound, NaiveDate, NaiveDateTime, TimeDelta, Utc};
+use entity::{movie, room, seat_hold, showtime, showtime_room, taken_seat, theater};
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::Set,
+    ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait,
+    FromQueryResult, IntoActiveModel, JoinType, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, QueryTrait, RelationTrait, TransactionTrait,
+    prelude::DateTimeWithTimeZone,
+    sea_query::{Expr, ExprTrait, Func, Query, SelectStatement, extension::postgres::PgExpr},
+};
 use std::{collections::HashMap, str::FromStr};
 use uuid::Uuid;
 
 use crate::{
+    app_error::{AppError, FieldError},
     app_state::Result,
-    models::showtime_model::{Movie, Showtime, ShowtimeRoom, Theater},
+    extractors::authenticated_user::AuthenticatedUser,
+    models::{
+        requests::{
+            create_showtime_request_model::{
+                AddShowtimeSlotsRequest, CreateShowtimeRequest, ShowtimeSlotRequest,
+            },
+            get_showtime_request_model::GetShowtimeQueryParams,
+        },
+        showtime_model::{Movie, Showtime, ShowtimeRoom, TakenSeats, Theater},
+    },
 };
 
-pub fn map_showtime(query_results: Vec<serde_json::Value>) -> Result<Vec<Showtime>> {
-    if query_results.is_empty() {
+use super::{
+    genres_service::find_movie_genres,
+    movies_service::{MAX_RUNTIME_MINUTES, parse_filter},
+    pagination_service::{Info, validate_pagination},
+    timezone_service::{find_timezone, from_local_time, to_local_time},
+};
+
+// A slot together with the room and theater it takes place in
+#[derive(FromQueryResult)]
+struct SlotRow {
+    id: i32,
+    showtime_id: Uuid,
+    time: DateTimeWithTimeZone,
+    price: i32,
+    room_id: Uuid,
+    room_name: String,
+    theater_id: Uuid,
+    timezone: String,
+}
+
+/// Ids of the showtimes with at least one slot matching `slot_filter`, which may filter on
+/// `showtime_room`, `room` and `theater` columns.
+pub fn showtimes_with_slots(slot_filter: Condition) -> SelectStatement {
+    Query::select()
+        .column((showtime_room::Entity, showtime_room::Column::ShowtimeId))
+        .from(showtime_room::Entity)
+        .inner_join(
+            room::Entity,
+            Expr::col((room::Entity, room::Column::Id))
+                .equals((showtime_room::Entity, showtime_room::Column::RoomId)),
+        )
+        .inner_join(
+            theater::Entity,
+            Expr::col((theater::Entity, theater::Column::Id))
+                .equals((room::Entity, room::Column::TheaterId)),
+        )
+        .cond_where(slot_filter)
+        .to_owned()
+}
+
+/// Builds the showtimes in the order they are given, each with its slots that match
+/// `slot_filter` (see [`showtimes_with_slots`]) ordered by time.
+pub async fn load_showtimes<C: ConnectionTrait>(
+    db: &C,
+    showtimes: Vec<showtime::Model>,
+    slot_filter: Condition,
+) -> Result<Vec<Showtime>> {
+    if showtimes.is_empty() {
         return Ok(vec![]);
     }
 
-    let mut results: Vec<Showtime> = vec![];
-    let mut grouped_results: HashMap<String, Vec<Value>> = HashMap::new();
-
-    // Group all rows by the showtime ID first. This is simpler than comparing with the next item.
-    for row in query_results {
-        let id = row
-            .get("id")
-            .and_then(Value::as_str)
-            .ok_or_else(|| anyhow!("'id' field is missing or not a string in query result"))?
-            .to_string();
-        grouped_results.entry(id).or_default().push(row);
+    let showtime_ids = showtimes.iter().map(|sh| sh.id).collect::<Vec<_>>();
+    let mut movie_ids = showtimes.iter().map(|sh| sh.movie_id).collect::<Vec<_>>();
+    movie_ids.sort();
+    movie_ids.dedup();
+
+    let slots = showtime_room::Entity::find()
+        .select_only()
+        .columns([
+            showtime_room::Column::Id,
+            showtime_room::Column::ShowtimeId,
+            showtime_room::Column::Time,
+            showtime_room::Column::Price,
+            showtime_room::Column::RoomId,
+        ])
+        .column_as(room::Column::Name, "room_name")
+        .column_as(room::Column::TheaterId, "theater_id")
+        .column_as(theater::Column::Timezone, "timezone")
+        .join(JoinType::InnerJoin, showtime_room::Relation::Room.def())
+        .join(JoinType::InnerJoin, room::Relation::Theater.def())
+        .filter(showtime_room::Column::ShowtimeId.is_in(showtime_ids))
+        .filter(slot_filter)
+        .order_by_asc(showtime_room::Column::Time)
+        .order_by_asc(showtime_room::Column::Id)
+        .into_model::<SlotRow>()
+        .all(db)
+        .await?;
+
+    let mut theater_ids = slots.iter().map(|slot| slot.theater_id).collect::<Vec<_>>();
+    theater_ids.sort();
+    theater_ids.dedup();
+
+    let theaters = theater::Entity::find()
+        .filter(theater::Column::Id.is_in(theater_ids))
+        .order_by_asc(theater::Column::Name)
+        .all(db)
+        .await?;
+
+    let movies = movie::Entity::find()
+        .filter(movie::Column::Id.is_in(movie_ids.clone()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|movie| (movie.id, movie))
+        .collect::<HashMap<_, _>>();
+
+    let genres = find_movie_genres(db, &movie_ids).await?;
+
+    let mut slots_by_showtime: HashMap<Uuid, Vec<SlotRow>> = HashMap::new();
+    for slot in slots {
+        slots_by_showtime
+            .entry(slot.showtime_id)
+            .or_default()
+            .push(slot);
     }
 
-    for (id, rows) in grouped_results {
-        let first_row = &rows[0]; // All rows in this group share the main showtime info.
-
-        let mut theaters = HashMap::new();
-        let mut showtime_rooms = HashMap::new();
-
-        for row in &rows {
-            // Use and_then to safely chain operations that return Option.
-            let t_id = row.get("t_id").and_then(Value::as_str).unwrap_or_default();
-            if !theaters.contains_key(t_id) {
-                theaters.insert(
-                    t_id.to_string(),
-                    Theater {
-                        id: t_id.to_string(),
-                        name: row
-                            .get("t_name")
-                            .and_then(Value::as_str)
-                            .unwrap_or_default()
-                            .to_string(),
-                        location: row
-                            .get("t_location")
-                            .and_then(Value::as_str)
-                            .unwrap_or_default()
-                            .to_string(),
-                    },
-                );
-            }
+    showtimes
+        .into_iter()
+        .map(|showtime| {
+            let movie = movies
+                .get(&showtime.movie_id)
+                .ok_or_else(|| anyhow!("Movie of showtime: {} is missing", showtime.id))?;
+            let slots = slots_by_showtime.remove(&showtime.id).unwrap_or_default();
 
-            if let Some(shr_id) = row.get("shr_id").and_then(Value::as_u64) {
-                if !showtime_rooms.contains_key(&shr_id) {
-                    let shr_time_str = row
-                        .get("shr_time")
-                        .and_then(Value::as_str)
-                        .ok_or_else(|| anyhow!("'shr_time' is missing or is not parseable"))?;
-
-                    let shr = ShowtimeRoom {
-                        id: shr_id,
-                        price: row
-                            .get("shr_price")
-                            .and_then(Value::as_i64)
-                            .ok_or_else(|| anyhow!("'shr_price' is missing or is not parseable"))?
-                            as u32,
-                        time: NaiveDateTime::parse_from_str(shr_time_str, "%Y-%m-%dT%H:%M:%S")
-                            .context(format!("Failed to parse shr_time: {}", shr_time_str))?,
-                        room_id: row
-                            .get("shr_room_id")
-                            .and_then(Value::as_str)
-                            .ok_or_else(|| anyhow!("'shr_room_id' is missing or is not parseable"))?
-                            .to_string(),
-                        room_name: row
-                            .get("shr_room_name")
-                            .and_then(Value::as_str)
-                            .ok_or_else(|| {
-                                anyhow!("'shr_room_name' is missing or is not parseable")
-                            })?
-                            .to_string(),
-                    };
-                    showtime_rooms.insert(shr_id, shr);
-                }
+            let theaters = theaters
+                .iter()
+                .filter(|t| slots.iter().any(|slot| slot.theater_id == t.id))
+                .map(|t| Theater {
+                    id: t.id.to_string(),
+                    name: t.name.to_owned(),
+                    location: t.location.to_owned(),
+                    timezone: t.timezone.to_owned(),
+                })
+                .collect();
+
+            let showtime_rooms = slots
+                .into_iter()
+                .map(|slot| {
+                    let time = slot.time.with_timezone(&Utc);
+
+                    Ok(ShowtimeRoom {
+                        id: slot.id as u64,
+                        time,
+                        local_time: to_local_time(time, &slot.timezone)?,
+                        timezone: slot.timezone,
+                        price: slot.price as u32,
+                        room_id: slot.room_id.to_string(),
+                        room_name: slot.room_name,
+                    })
+                })
+                .collect::<Result<_>>()?;
+
+            Ok(Showtime {
+                id: showtime.id.to_string(),
+                created_at: showtime.created_at,
+                updated_at: showtime.updated_at,
+                movie: Movie {
+                    id: movie.id.to_string(),
+                    title: movie.title.to_owned(),
+                    rating: movie.rating as f64,
+                    genres: genres.get(&movie.id).cloned().unwrap_or_default(),
+                    poster_url: movie.poster_url.to_owned(),
+                },
+                showtime_rooms,
+                theaters,
+            })
+        })
+        .collect()
+}
+
+// Parses a time without an offset, such as `2026-10-25T19:30`.
+fn parse_naive_time(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M"))
+        .ok()
+}
+
+// Accepts a time with an offset, a UTC time without one, or a UTC date that stands for the start
+// or the end of that day.
+fn parse_slot_time(
+    field: &str,
+    value: Option<&String>,
+    end_of_day: bool,
+    errors: &mut Vec<FieldError>,
+) -> Option<DateTime<Utc>> {
+    let value = value?.trim();
+
+    let time = DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc))
+        .ok()
+        .or_else(|| parse_naive_time(value).map(|time| time.and_utc()))
+        .or_else(|| {
+            let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+            match end_of_day {
+                true => date.and_hms_opt(23, 59, 59),
+                false => date.and_hms_opt(0, 0, 0),
             }
-        }
+            .map(|time| time.and_utc())
+        });
 
-        let created_at_str = first_row
-            .get("created_at")
-            .and_then(Value::as_str)
-            .ok_or_else(|| anyhow!("'created_at' is missing"))?;
-        let updated_at_str = first_row
-            .get("updated_at")
-            .and_then(Value::as_str)
-            .ok_or_else(|| anyhow!("'updated_at' is missing"))?;
-
-        let sh = Showtime {
-            id,
-            created_at: NaiveDateTime::parse_from_str(created_at_str, "%Y-%m-%dT%H:%M:%S.%f")
-                .context(format!("Failed to parse created_at: {}", created_at_str))?,
-            updated_at: NaiveDateTime::parse_from_str(updated_at_str, "%Y-%m-%dT%H:%M:%S.%f")
-                .context(format!("Failed to parse updated_at: {}", updated_at_str))?,
-            movie: Movie {
-                id: first_row
-                    .get("m_id")
-                    .and_then(Value::as_str)
-                    .unwrap_or_default()
-                    .to_string(),
-                title: first_row
-                    .get("m_title")
-                    .and_then(Value::as_str)
-                    .unwrap_or_default()
-                    .to_string(),
-                genre: first_row
-                    .get("m_genre")
-                    .and_then(Value::as_str)
-                    .unwrap_or_default()
-                    .to_string(),
-                rating: first_row
-                    .get("m_rating")
-                    .and_then(Value::as_f64)
-                    .unwrap_or_default(),
-                poster_url: first_row
-                    .get("m_poster_url")
-                    .and_then(Value::as_str)
-                    .unwrap_or_default()
-                    .to_string(),
-            },
-            theaters: theaters.into_values().collect(),
-            showtime_rooms: showtime_rooms.into_values().collect(),
-        };
+    if time.is_none() {
+        errors.push(FieldError {
+            field: field.to_string(),
+            message: format!("{value} is not a date (YYYY-MM-DD) or a time (YYYY-MM-DDTHH:MM)"),
+        });
+    }
+
+    time
+}
 
-        results.push(sh);
+/// Lists showtimes, newest first, with only the slots that match the filters. Showtimes without
+/// a matching slot are left out.
+pub async fn get_showtime(
+    db: &DatabaseConnection,
+    options: GetShowtimeQueryParams,
+) -> Result<(Vec<Showtime>, Info)> {
+    let mut errors = vec![];
+
+    validate_pagination(options.limit, options.page, &mut errors);
+
+    let from = parse_slot_time("from", options.from.as_ref(), false, &mut errors);
+    let to = parse_slot_time("to", options.to.as_ref(), true, &mut errors);
+    if let (Some(from), Some(to)) = (from, to)
+        && from > to
+    {
+        errors.push(FieldError {
+            field: "from".to_string(),
+            message: "from must not be after to".to_string(),
+        });
     }
 
-    Ok(results)
-}
-
-pub async fn get_showtime(db: &DatabaseConnection) -> Result<Vec<Showtime>> {
-    let showtime_query_results = showtime::Entity::find()
-        .from_raw_sql(raw_sql!(
-            Postgres,
-            r#"
-        SELECT sh.id,
-       created_at,
-       updated_at,
-       m.id           as m_id,
-       m.title        as m_title,
-       m.rating       as m_rating,
-       m.genre        as m_genre,
-       m.poster_url   as m_poster_url,
-       shr.id         as shr_id,
-       shr.time       as shr_time,
-       shr.price      as shr_price,
-       r.id           as shr_room_id,
-       r.name         as shr_room_name,
-       t.id           as t_id,
-       t.name         as t_name,
-       t.location     as t_location
-FROM showtime sh
-         JOIN movie m ON m.id = sh.movie_id
-         JOIN showtime_room shr ON shr.showtime_id = sh.id
-         JOIN room r ON r.id = shr.room_id
-         JOIN theater t ON t.id = r.theater_id
-ORDER BY created_at DESC;
-        "#
-        ))
-        .into_json()
-        .all(db)
+    let movie_id = parse_filter::<Uuid>("movieId", options.movie_id.as_ref(), "an id", &mut errors);
+    let theater_id = parse_filter::<Uuid>(
+        "theaterId",
+        options.theater_id.as_ref(),
+        "an id",
+        &mut errors,
+    );
+
+    // Theaters without a structured city fall back to matching the location, where `%` and `_`
+    // in the city are matched literally
+    let city_filter = options
+        .city
+        .as_ref()
+        .map(|city| city.trim())
+        .filter(|city| !city.is_empty())
+        .map(|city| {
+            let pattern = city
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            Condition::any()
+                .add(
+                    Expr::expr(Func::lower(Expr::col((
+                        theater::Entity,
+                        theater::Column::City,
+                    ))))
+                    .eq(city.to_lowercase()),
+                )
+                .add(
+                    Condition::all()
+                        .add(Expr::col((theater::Entity, theater::Column::City)).is_null())
+                        .add(
+                            Expr::col((theater::Entity, theater::Column::Location))
+                                .ilike(format!("%{pattern}%")),
+                        ),
+                )
+        });
+
+    let prices = [
+        ("minPrice", options.min_price.as_ref()),
+        ("maxPrice", options.max_price.as_ref()),
+    ]
+    .map(|(field, value)| {
+        parse_filter::<i32>(field, value, "a whole number", &mut errors).filter(|price| {
+            if *price < 0 {
+                errors.push(FieldError {
+                    field: field.to_string(),
+                    message: "Price must not be negative".to_string(),
+                });
+            }
+            *price >= 0
+        })
+    });
+    let [min_price, max_price] = prices;
+    if let (Some(min_price), Some(max_price)) = (min_price, max_price)
+        && min_price > max_price
+    {
+        errors.push(FieldError {
+            field: "minPrice".to_string(),
+            message: "minPrice must not be greater than maxPrice".to_string(),
+        });
+    }
+
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let slot_filter = Condition::all()
+        .add_option(from.map(|from| showtime_room::Column::Time.gte(from)))
+        .add_option(to.map(|to| showtime_room::Column::Time.lte(to)))
+        .add_option(theater_id.map(|theater_id| theater::Column::Id.eq(theater_id)))
+        .add_option(city_filter)
+        .add_option(min_price.map(|min_price| showtime_room::Column::Price.gte(min_price)))
+        .add_option(max_price.map(|max_price| showtime_room::Column::Price.lte(max_price)));
+
+    let query = showtime::Entity::find()
+        .filter(showtime::Column::Id.in_subquery(showtimes_with_slots(slot_filter.clone())))
+        .apply_if(movie_id, |query, movie_id| {
+            query.filter(showtime::Column::MovieId.eq(movie_id))
+        })
+        .order_by_desc(showtime::Column::CreatedAt)
+        .order_by_asc(showtime::Column::Id);
+
+    let total_items = query.clone().count(db).await?;
+
+    let showtimes = query
+        .paginate(db, options.limit)
+        .fetch_page(options.page - 1)
         .await?;
 
-    map_showtime(showtime_query_results).map_err(|e| e.into())
+    let showtime = load_showtimes(db, showtimes, slot_filter).await?;
+
+    Ok((
+        showtime,
+        Info::new(options.page, options.limit, total_items),
+    ))
 }
 
 pub async fn get_taken_seats(
     db: &DatabaseConnection,
     showtime_id: String,
     showtime_room_id: i32,
-) -> Result<Vec<String>> {
+) -> Result<TakenSeats> {
     let showtime_id = Uuid::from_str(&showtime_id)?;
 
     let seat_taken = taken_seat::Entity::find()
@@ -199,5 +368,391 @@ pub async fn get_taken_seats(
         .map(|st| st.seat_identifier.to_owned())
         .collect::<Vec<_>>();
 
-    Ok(seat_taken)
+    let seat_held = seat_hold::Entity::find()
+        .filter(
+            Condition::all()
+                .add(seat_hold::Column::ShowtimeId.eq(showtime_id))
+                .add(seat_hold::Column::ShowtimeRoomId.eq(showtime_room_id))
+                .add(seat_hold::Column::ExpiresAt.gt(Utc::now().naive_utc())),
+        )
+        .all(db)
+        .await?
+        .iter()
+        .map(|sh| sh.seat_identifier.to_owned())
+        .collect::<Vec<_>>();
+
+    Ok(TakenSeats {
+        sold: seat_taken,
+        held: seat_held,
+    })
+}
+
+enum SlotTime {
+    Instant(DateTime<Utc>),
+    // Wall-clock time in the timezone of the room's theater
+    Local(NaiveDateTime),
+}
+
+struct RequestedSlot {
+    room_id: Uuid,
+    time: SlotTime,
+    price: i32,
+}
+
+struct Slot {
+    room_id: Uuid,
+    time: DateTime<Utc>,
+    price: i32,
+}
+
+fn parse_slots(slots: Vec<ShowtimeSlotRequest>) -> Result<Vec<RequestedSlot>> {
+    let mut errors = vec![];
+
+    if slots.is_empty() {
+        errors.push(FieldError {
+            field: "slots".to_string(),
+            message: "At least one slot must be scheduled".to_string(),
+        });
+    }
+
+    let mut parsed = vec![];
+
+    for (index, slot) in slots.into_iter().enumerate() {
+        let room_id = Uuid::from_str(&slot.room_id);
+        if room_id.is_err() {
+            errors.push(FieldError {
+                field: format!("slots[{index}].roomId"),
+                message: format!("{} is not a valid room id", slot.room_id),
+            });
+        }
+
+        let value = slot.time.trim();
+        let time = DateTime::parse_from_rfc3339(value)
+            .map(|time| SlotTime::Instant(time.with_timezone(&Utc)))
+            .ok()
+            .or_else(|| parse_naive_time(value).map(SlotTime::Local));
+        if time.is_none() {
+            errors.push(FieldError {
+                field: format!("slots[{index}].time"),
+                message: format!(
+                    "{value} is not a time such as 2026-10-25T19:30 or 2026-10-25T12:30:00Z"
+                ),
+            });
+        }
+
+        if slot.price < 0 {
+            errors.push(FieldError {
+                field: format!("slots[{index}].price"),
+                message: "Price must not be negative".to_string(),
+            });
+        }
+
+        if let (Ok(room_id), Some(time)) = (room_id, time) {
+            parsed.push(RequestedSlot {
+                room_id,
+                time,
+                price: slot.price,
+            });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(parsed)
+    } else {
+        Err(AppError::Validation(errors))
+    }
+}
+
+// Turns wall-clock slot times into instants using the timezone of each room's theater, and
+// checks that every slot is in the future.
+fn resolve_slots(
+    slots: Vec<RequestedSlot>,
+    room_timezones: &HashMap<Uuid, String>,
+) -> Result<Vec<Slot>> {
+    let mut errors = vec![];
+    let mut resolved = vec![];
+    let now = Utc::now();
+
+    for (index, slot) in slots.into_iter().enumerate() {
+        let time = match slot.time {
+            SlotTime::Instant(time) => Some(time),
+            SlotTime::Local(time) => {
+                let timezone = room_timezones
+                    .get(&slot.room_id)
+                    .ok_or_else(|| anyhow!("Timezone of room: {} is missing", slot.room_id))?;
+                let tz = find_timezone(timezone)
+                    .ok_or_else(|| anyhow!("Unknown timezone: {timezone}"))?;
+
+                let instant = from_local_time(time, &tz);
+                if instant.is_none() {
+                    errors.push(FieldError {
+                        field: format!("slots[{index}].time"),
+                        message: format!(
+                            "{time} is skipped or repeated in {timezone} by a clock change, send it with an offset"
+                        ),
+                    });
+                }
+                instant
+            }
+        };
+
+        let Some(time) = time else {
+            continue;
+        };
+
+        // Showtimes are scheduled to the minute
+        let time = time
+            .duration_trunc(TimeDelta::minutes(1))
+            .map_err(|e| anyhow!("Failed to truncate slot time: {e}"))?;
+        if time <= now {
+            errors.push(FieldError {
+                field: format!("slots[{index}].time"),
+                message: "Time must be in the future".to_string(),
+            });
+        }
+
+        resolved.push(Slot {
+            room_id: slot.room_id,
+            time,
+            price: slot.price,
+        });
+    }
+
+    if errors.is_empty() {
+        Ok(resolved)
+    } else {
+        Err(AppError::Validation(errors))
+    }
+}
+
+// A slot keeps its room busy for the runtime of the movie plus the time needed to clean up.
+// Slots of movies without a runtime only count the cleaning buffer.
+fn slot_end(
+    time: DateTime<Utc>,
+    runtime_minutes: Option<i32>,
+    buffer_minutes: i64,
+) -> DateTime<Utc> {
+    time + TimeDelta::minutes(runtime_minutes.unwrap_or_default() as i64 + buffer_minutes)
+}
+
+/// Adds `slots` to the showtime, refusing any slot that would overlap another one in the
+/// same room.
+async fn schedule_slots(
+    txn: &DatabaseTransaction,
+    actor: &AuthenticatedUser,
+    buffer_minutes: i64,
+    showtime: &showtime::Model,
+    movie: &movie::Model,
+    slots: Vec<ShowtimeSlotRequest>,
+) -> Result<()> {
+    let slots = parse_slots(slots)?;
+
+    let runtime_minutes = movie.runtime_minutes.ok_or_else(|| {
+        AppError::Validation(vec![FieldError {
+            field: "movieId".to_string(),
+            message: format!(
+                "Movie {} needs a runtime before it can be scheduled",
+                movie.title
+            ),
+        }])
+    })?;
+
+    // Locking the rooms serialises scheduling, so two overlapping slots cannot both get in
+    let room_ids = slots.iter().map(|slot| slot.room_id).collect::<Vec<_>>();
+    let rooms = room::Entity::find()
+        .filter(room::Column::Id.is_in(room_ids.clone()))
+        .lock_exclusive()
+        .all(txn)
+        .await?
+        .into_iter()
+        .map(|room| (room.id, room))
+        .collect::<HashMap<_, _>>();
+
+    for slot in &slots {
+        let room = rooms.get(&slot.room_id).ok_or_else(|| {
+            AppError::NotFound(format!("Room with id: {} does not exist", slot.room_id))
+        })?;
+        actor.require_theater_manager(room.theater_id)?;
+    }
+
+    let theater_timezones = theater::Entity::find()
+        .filter(theater::Column::Id.is_in(rooms.values().map(|room| room.theater_id)))
+        .all(txn)
+        .await?
+        .into_iter()
+        .map(|theater| (theater.id, theater.timezone))
+        .collect::<HashMap<_, _>>();
+    let room_timezones = rooms
+        .values()
+        .filter_map(|room| {
+            theater_timezones
+                .get(&room.theater_id)
+                .map(|timezone| (room.id, timezone.to_owned()))
+        })
+        .collect::<HashMap<_, _>>();
+
+    let slots = resolve_slots(slots, &room_timezones)?;
+
+    let earliest = slots.iter().map(|slot| slot.time).min().unwrap_or_default();
+    let latest_end = slots
+        .iter()
+        .map(|slot| slot_end(slot.time, Some(runtime_minutes), buffer_minutes))
+        .max()
+        .unwrap_or_default();
+
+    let existing_slots = showtime_room::Entity::find()
+        .select_only()
+        .column(showtime_room::Column::RoomId)
+        .column(showtime_room::Column::Time)
+        .column(movie::Column::RuntimeMinutes)
+        .join(JoinType::InnerJoin, showtime_room::Relation::Showtime.def())
+        .join(JoinType::InnerJoin, showtime::Relation::Movie.def())
+        .filter(
+            Condition::all()
+                .add(showtime_room::Column::RoomId.is_in(room_ids))
+                .add(showtime_room::Column::Time.lt(latest_end))
+                .add(
+                    showtime_room::Column::Time
+                        .gt(earliest
+                            - TimeDelta::minutes(MAX_RUNTIME_MINUTES as i64 + buffer_minutes)),
+                ),
+        )
+        .into_tuple::<(Uuid, DateTimeWithTimeZone, Option<i32>)>()
+        .all(txn)
+        .await?
+        .into_iter()
+        .map(|(room_id, time, runtime)| (room_id, time.with_timezone(&Utc), runtime))
+        .collect::<Vec<_>>();
+
+    let mut conflicts = vec![];
+    for (index, slot) in slots.iter().enumerate() {
+        let end = slot_end(slot.time, Some(runtime_minutes), buffer_minutes);
+        let room = &rooms[&slot.room_id];
+
+        let requested = slots[..index]
+            .iter()
+            .filter(|other| other.room_id == slot.room_id)
+            .map(|other| (other.time, Some(runtime_minutes)));
+        let existing = existing_slots
+            .iter()
+            .filter(|(room_id, _, _)| *room_id == slot.room_id)
+            .map(|(_, time, runtime)| (*time, *runtime));
+
+        for (other_time, other_runtime) in requested.chain(existing) {
+            if slot.time < slot_end(other_time, other_runtime, buffer_minutes) && other_time < end {
+                conflicts.push(format!(
+                    "{} at {} overlaps the showtime at {}",
+                    room.name, slot.time, other_time
+                ));
+            }
+        }
+    }
+
+    if !conflicts.is_empty() {
+        return Err(AppError::Conflict(conflicts.join("; ")));
+    }
+
+    showtime_room::Entity::insert_many(slots.iter().map(|slot| showtime_room::ActiveModel {
+        time: Set(slot.time.fixed_offset()),
+        price: Set(slot.price),
+        room_id: Set(slot.room_id),
+        showtime_id: Set(showtime.id),
+        ..Default::default()
+    }))
+    .exec(txn)
+    .await?;
+
+    Ok(())
+}
+
+async fn load_showtime<C: ConnectionTrait>(db: &C, showtime: showtime::Model) -> Result<Showtime> {
+    let showtime_id = showtime.id;
+
+    load_showtimes(db, vec![showtime], Condition::all())
+        .await?
+        .pop()
+        .ok_or_else(|| anyhow!("Showtime with id: {} could not be loaded", showtime_id).into())
+}
+
+pub async fn create_showtime(
+    db: &DatabaseConnection,
+    actor: &AuthenticatedUser,
+    buffer_minutes: i64,
+    request: CreateShowtimeRequest,
+) -> Result<Showtime> {
+    let movie_id = Uuid::from_str(&request.movie_id)?;
+
+    let movie = movie::Entity::find_by_id(movie_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Movie with id: {} does not exist", movie_id)))?;
+
+    let txn = db.begin().await?;
+
+    let now = Utc::now().naive_utc();
+    let showtime = showtime::ActiveModel {
+        id: Set(Uuid::now_v7()),
+        movie_id: Set(movie.id),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(&txn)
+    .await?;
+
+    schedule_slots(
+        &txn,
+        actor,
+        buffer_minutes,
+        &showtime,
+        &movie,
+        request.slots,
+    )
+    .await?;
+
+    let showtime = load_showtime(&txn, showtime).await?;
+
+    txn.commit().await?;
+
+    Ok(showtime)
+}
+
+pub async fn add_showtime_slots(
+    db: &DatabaseConnection,
+    actor: &AuthenticatedUser,
+    buffer_minutes: i64,
+    showtime_id: String,
+    request: AddShowtimeSlotsRequest,
+) -> Result<Showtime> {
+    let showtime_id = Uuid::from_str(&showtime_id)?;
+
+    let (showtime, movie) = showtime::Entity::find_by_id(showtime_id)
+        .find_also_related(movie::Entity)
+        .one(db)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("Showtime with id: {} does not exist", showtime_id))
+        })?;
+    let movie = movie.ok_or_else(|| anyhow!("Movie of showtime: {} is missing", showtime_id))?;
+
+    let txn = db.begin().await?;
+
+    schedule_slots(
+        &txn,
+        actor,
+        buffer_minutes,
+        &showtime,
+        &movie,
+        request.slots,
+    )
+    .await?;
+
+    let mut showtime = showtime.into_active_model();
+    showtime.updated_at = Set(Utc::now().naive_utc());
+    let showtime = showtime.update(&txn).await?;
+
+    let showtime = load_showtime(&txn, showtime).await?;
+
+    txn.commit().await?;
+
+    Ok(showtime)
 }
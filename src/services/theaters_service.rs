@@ -1,29 +1,489 @@
 use std::str::FromStr;
 
-use entity::theater;
-use sea_orm::{DatabaseConnection, EntityTrait, raw_sql};
+use chrono::Utc;
+use entity::{room, showtime, showtime_room, theater};
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::Set,
+    ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait, FromQueryResult,
+    IntoActiveModel, JoinType, Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+    QueryTrait, RelationTrait, TransactionTrait,
+    prelude::DateTimeWithTimeZone,
+    raw_sql,
+    sea_query::{Expr, ExprTrait},
+};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::{
+    app_error::{AppError, FieldError},
     app_state::Result,
-    models::{showtime_model::Showtime, theater_model::Theater},
+    extractors::authenticated_user::AuthenticatedUser,
+    models::{
+        requests::{
+            create_theater_request_model::CreateTheaterRequest,
+            get_theaters_request_model::GetTheatersQueryParams,
+            update_theater_request_model::UpdateTheaterRequest,
+        },
+        showtime_model::Showtime,
+        theater_model::{NextShowtime, Theater},
+    },
 };
 
-use super::showtime_service::map_showtime;
+use super::{
+    movies_service::{normalize_text, parse_filter},
+    showtime_service::{load_showtimes, showtimes_with_slots},
+    timezone_service::{find_timezone, to_local_time},
+};
 
-pub async fn get_theaters(db: &DatabaseConnection) -> Result<Vec<Theater>> {
-    let theaters = theater::Entity::find()
-        .all(db)
-        .await?
-        .iter()
-        .map(|t| Theater {
-            id: t.id.to_string(),
-            name: t.name.to_owned(),
-            location: t.location.to_owned(),
+fn map_theater(theater: theater::Model) -> Theater {
+    Theater {
+        id: theater.id.to_string(),
+        name: theater.name,
+        location: theater.location,
+        timezone: theater.timezone,
+        latitude: theater.latitude,
+        longitude: theater.longitude,
+        address: theater.address,
+        city: theater.city,
+        distance_km: None,
+        next_showtimes: None,
+    }
+}
+
+struct TheaterFields {
+    name: String,
+    location: String,
+    timezone: String,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    address: Option<String>,
+    city: Option<String>,
+}
+
+fn validate_coordinates(
+    latitude: f64,
+    longitude: f64,
+    (latitude_field, longitude_field): (&str, &str),
+    errors: &mut Vec<FieldError>,
+) {
+    if !(-90.0..=90.0).contains(&latitude) {
+        errors.push(FieldError {
+            field: latitude_field.to_string(),
+            message: "Latitude must be between -90 and 90".to_string(),
+        });
+    }
+
+    if !(-180.0..=180.0).contains(&longitude) {
+        errors.push(FieldError {
+            field: longitude_field.to_string(),
+            message: "Longitude must be between -180 and 180".to_string(),
+        });
+    }
+}
+
+fn validate_theater(fields: TheaterFields) -> Result<TheaterFields> {
+    let fields = TheaterFields {
+        name: fields.name.trim().to_string(),
+        location: fields.location.trim().to_string(),
+        timezone: fields.timezone.trim().to_string(),
+        address: normalize_text(fields.address),
+        city: normalize_text(fields.city),
+        ..fields
+    };
+    let mut errors = vec![];
+
+    if fields.name.is_empty() {
+        errors.push(FieldError {
+            field: "name".to_string(),
+            message: "Name must not be empty".to_string(),
+        });
+    }
+
+    if fields.location.is_empty() {
+        errors.push(FieldError {
+            field: "location".to_string(),
+            message: "Location must not be empty".to_string(),
+        });
+    }
+
+    if find_timezone(&fields.timezone).is_none() {
+        errors.push(FieldError {
+            field: "timezone".to_string(),
+            message: format!(
+                "{} is not an IANA timezone, e.g. Asia/Jakarta",
+                fields.timezone
+            ),
+        });
+    }
+
+    match (fields.latitude, fields.longitude) {
+        (Some(latitude), Some(longitude)) => {
+            validate_coordinates(latitude, longitude, ("latitude", "longitude"), &mut errors)
+        }
+        (None, None) => {}
+        (None, Some(_)) => errors.push(FieldError {
+            field: "latitude".to_string(),
+            message: "Latitude is required together with the longitude".to_string(),
+        }),
+        (Some(_), None) => errors.push(FieldError {
+            field: "longitude".to_string(),
+            message: "Longitude is required together with the latitude".to_string(),
+        }),
+    }
+
+    if errors.is_empty() {
+        Ok(fields)
+    } else {
+        Err(AppError::Validation(errors))
+    }
+}
+
+pub async fn find_theater<C: ConnectionTrait>(
+    db: &C,
+    theater_id: Uuid,
+    lock: bool,
+) -> Result<theater::Model> {
+    let mut query = theater::Entity::find_by_id(theater_id);
+    if lock {
+        query = query.lock_exclusive();
+    }
+
+    query.one(db).await?.ok_or_else(|| {
+        AppError::NotFound(format!("Theater with id: {} does not exist", theater_id))
+    })
+}
+
+/// Most upcoming slots that can be listed per theater.
+const MAX_NEXT_SHOWTIMES: u64 = 10;
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+#[derive(FromQueryResult)]
+struct TheaterWithDistance {
+    #[sea_orm(nested)]
+    theater: theater::Model,
+    distance_km: f64,
+}
+
+#[derive(FromQueryResult)]
+struct NextShowtimeRow {
+    theater_id: Uuid,
+    showtime_id: Uuid,
+    showtime_room_id: i32,
+    movie_id: Uuid,
+    movie_title: String,
+    room_name: String,
+    time: DateTimeWithTimeZone,
+    timezone: String,
+}
+
+/// Parses `latitude,longitude` into a pair of coordinates.
+fn parse_point(
+    field: &str,
+    value: Option<&String>,
+    errors: &mut Vec<FieldError>,
+) -> Option<(f64, f64)> {
+    let value = value?.trim();
+    let point = value.split_once(',').and_then(|(latitude, longitude)| {
+        Some((
+            latitude.trim().parse().ok()?,
+            longitude.trim().parse().ok()?,
+        ))
+    });
+
+    match point {
+        Some((latitude, longitude)) => {
+            let error_count = errors.len();
+            validate_coordinates(latitude, longitude, (field, field), errors);
+            (errors.len() == error_count).then_some((latitude, longitude))
+        }
+        None => {
+            errors.push(FieldError {
+                field: field.to_string(),
+                message: format!("{value} is not a latitude,longitude pair"),
+            });
+            None
+        }
+    }
+}
+
+/// Haversine distance in kilometres between the theater and the point.
+fn distance_km((latitude, longitude): (f64, f64)) -> Expr {
+    Expr::cust_with_values(
+        r#"$1 * 2 * asin(least(1, sqrt(
+            power(sin(radians("theater"."latitude" - $2) / 2), 2)
+            + cos(radians($2)) * cos(radians("theater"."latitude"))
+            * power(sin(radians("theater"."longitude" - $3) / 2), 2)
+        )))"#,
+        [EARTH_RADIUS_KM, latitude, longitude],
+    )
+}
+
+/// The next `limit` slots of each of the theaters, soonest first.
+async fn find_next_showtimes(
+    db: &DatabaseConnection,
+    theater_ids: Vec<Uuid>,
+    limit: u64,
+) -> Result<HashMap<Uuid, Vec<NextShowtime>>> {
+    let limit = limit as i64;
+    let rows = NextShowtimeRow::find_by_statement(raw_sql!(
+        Postgres,
+        r#"
+        SELECT theater_id, showtime_id, showtime_room_id, movie_id, movie_title, room_name, time, timezone
+        FROM (SELECT r.theater_id,
+                     s.id AS showtime_id,
+                     shr.id AS showtime_room_id,
+                     m.id AS movie_id,
+                     m.title AS movie_title,
+                     r.name AS room_name,
+                     shr.time,
+                     t.timezone,
+                     row_number() OVER (PARTITION BY r.theater_id ORDER BY shr.time, shr.id) AS position
+              FROM showtime_room shr
+                       JOIN room r ON r.id = shr.room_id
+                       JOIN theater t ON t.id = r.theater_id
+                       JOIN showtime s ON s.id = shr.showtime_id
+                       JOIN movie m ON m.id = s.movie_id
+              WHERE r.theater_id = ANY({theater_ids})
+                AND shr.time > now()) next
+        WHERE position <= {limit}
+        ORDER BY time, showtime_room_id
+        "#
+    ))
+    .all(db)
+    .await?;
+
+    let mut next_showtimes: HashMap<Uuid, Vec<NextShowtime>> = HashMap::new();
+    for row in rows {
+        let time = row.time.with_timezone(&Utc);
+        next_showtimes
+            .entry(row.theater_id)
+            .or_default()
+            .push(NextShowtime {
+                showtime_id: row.showtime_id.to_string(),
+                showtime_room_id: row.showtime_room_id as u64,
+                movie_id: row.movie_id.to_string(),
+                movie_title: row.movie_title,
+                room_name: row.room_name,
+                time,
+                local_time: to_local_time(time, &row.timezone)?,
+            });
+    }
+
+    Ok(next_showtimes)
+}
+
+/// All theaters by name, or with `near` the theaters on the map ordered by distance from that
+/// point.
+pub async fn get_theaters(
+    db: &DatabaseConnection,
+    options: GetTheatersQueryParams,
+) -> Result<Vec<Theater>> {
+    let mut errors = vec![];
+
+    let near = parse_point("near", options.near.as_ref(), &mut errors);
+
+    let radius_km = parse_filter::<f64>(
+        "radius_km",
+        options.radius_km.as_ref(),
+        "a number",
+        &mut errors,
+    )
+    .filter(|radius_km| {
+        let is_positive = radius_km.is_finite() && *radius_km > 0.0;
+        if !is_positive {
+            errors.push(FieldError {
+                field: "radius_km".to_string(),
+                message: "Radius must be greater than 0".to_string(),
+            });
+        }
+        is_positive
+    });
+    if options.radius_km.is_some() && options.near.is_none() {
+        errors.push(FieldError {
+            field: "radius_km".to_string(),
+            message: "radius_km can only be used together with near".to_string(),
+        });
+    }
+
+    let next_showtimes = parse_filter::<u64>(
+        "next_showtimes",
+        options.next_showtimes.as_ref(),
+        "a whole number",
+        &mut errors,
+    )
+    .filter(|next_showtimes| {
+        let in_range = (1..=MAX_NEXT_SHOWTIMES).contains(next_showtimes);
+        if !in_range {
+            errors.push(FieldError {
+                field: "next_showtimes".to_string(),
+                message: format!("next_showtimes must be between 1 and {MAX_NEXT_SHOWTIMES}"),
+            });
+        }
+        in_range
+    });
+
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let theaters: Vec<(theater::Model, Option<f64>)> = match near {
+        Some(point) => theater::Entity::find()
+            .column_as(distance_km(point), "distance_km")
+            .filter(theater::Column::Latitude.is_not_null())
+            .filter(theater::Column::Longitude.is_not_null())
+            .apply_if(radius_km, |query, radius_km| {
+                query.filter(distance_km(point).lte(radius_km))
+            })
+            .order_by(distance_km(point), Order::Asc)
+            .order_by_asc(theater::Column::Name)
+            .into_model::<TheaterWithDistance>()
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|row| (row.theater, Some(row.distance_km)))
+            .collect(),
+        None => theater::Entity::find()
+            .order_by_asc(theater::Column::Name)
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|theater| (theater, None))
+            .collect(),
+    };
+
+    let mut next_showtimes = match next_showtimes {
+        Some(limit) => Some(
+            find_next_showtimes(
+                db,
+                theaters.iter().map(|(theater, _)| theater.id).collect(),
+                limit,
+            )
+            .await?,
+        ),
+        None => None,
+    };
+
+    Ok(theaters
+        .into_iter()
+        .map(|(theater, distance_km)| {
+            let theater_next_showtimes = next_showtimes
+                .as_mut()
+                .map(|next_showtimes| next_showtimes.remove(&theater.id).unwrap_or_default());
+            Theater {
+                distance_km,
+                next_showtimes: theater_next_showtimes,
+                ..map_theater(theater)
+            }
         })
-        .collect();
+        .collect())
+}
+
+pub async fn create_theater(
+    db: &DatabaseConnection,
+    request: CreateTheaterRequest,
+) -> Result<Theater> {
+    let fields = validate_theater(TheaterFields {
+        name: request.name,
+        location: request.location,
+        timezone: request.timezone.unwrap_or("UTC".to_string()),
+        latitude: request.latitude,
+        longitude: request.longitude,
+        address: request.address,
+        city: request.city,
+    })?;
+
+    let theater = theater::ActiveModel {
+        id: Set(Uuid::now_v7()),
+        name: Set(fields.name),
+        location: Set(fields.location),
+        timezone: Set(fields.timezone),
+        latitude: Set(fields.latitude),
+        longitude: Set(fields.longitude),
+        address: Set(fields.address),
+        city: Set(fields.city),
+    }
+    .insert(db)
+    .await?;
+
+    Ok(map_theater(theater))
+}
+
+pub async fn update_theater(
+    db: &DatabaseConnection,
+    actor: &AuthenticatedUser,
+    theater_id: String,
+    request: UpdateTheaterRequest,
+) -> Result<Theater> {
+    let theater_id = Uuid::from_str(&theater_id)?;
+    actor.require_theater_manager(theater_id)?;
 
-    Ok(theaters)
+    let theater = find_theater(db, theater_id, false).await?;
+
+    // The coordinates are replaced as a pair, so sending only one of them is an error
+    let (latitude, longitude) = match (request.latitude, request.longitude) {
+        (None, None) => (theater.latitude, theater.longitude),
+        coordinates => coordinates,
+    };
+    let fields = validate_theater(TheaterFields {
+        name: request.name.unwrap_or(theater.name.to_owned()),
+        location: request.location.unwrap_or(theater.location.to_owned()),
+        timezone: request.timezone.unwrap_or(theater.timezone.to_owned()),
+        latitude,
+        longitude,
+        address: request.address.or(theater.address.to_owned()),
+        city: request.city.or(theater.city.to_owned()),
+    })?;
+
+    let mut theater = theater.into_active_model();
+    theater.name = Set(fields.name);
+    theater.location = Set(fields.location);
+    theater.timezone = Set(fields.timezone);
+    theater.latitude = Set(fields.latitude);
+    theater.longitude = Set(fields.longitude);
+    theater.address = Set(fields.address);
+    theater.city = Set(fields.city);
+
+    let theater = theater.update(db).await?;
+
+    Ok(map_theater(theater))
+}
+
+/// Deleting a theater would cascade to its rooms and everything scheduled in them, so it is
+/// only allowed while none of its rooms has ever been scheduled.
+pub async fn delete_theater(db: &DatabaseConnection, theater_id: String) -> Result<()> {
+    let theater_id = Uuid::from_str(&theater_id)?;
+
+    let txn = db.begin().await?;
+
+    // Locking the theater keeps rooms from being added to it, and locking its rooms keeps them
+    // from being scheduled, until the theater is gone
+    let theater = find_theater(&txn, theater_id, true).await?;
+    room::Entity::find()
+        .filter(room::Column::TheaterId.eq(theater.id))
+        .lock_exclusive()
+        .all(&txn)
+        .await?;
+
+    let showtime_room_count = showtime_room::Entity::find()
+        .join(JoinType::InnerJoin, showtime_room::Relation::Room.def())
+        .filter(room::Column::TheaterId.eq(theater.id))
+        .count(&txn)
+        .await?;
+
+    if showtime_room_count > 0 {
+        return Err(AppError::Conflict(format!(
+            "Theater with id: {} still has {} showtime slots",
+            theater_id, showtime_room_count
+        )));
+    }
+
+    theater::Entity::delete_by_id(theater.id).exec(&txn).await?;
+
+    txn.commit().await?;
+
+    Ok(())
 }
 
 pub async fn get_theater_showtime(
@@ -32,38 +492,14 @@ pub async fn get_theater_showtime(
 ) -> Result<Vec<Showtime>> {
     let theater_id = Uuid::from_str(&theater_id)?;
 
-    let showtime_query_results = theater::Entity::find()
-        .from_raw_sql(raw_sql!(
-            Postgres,
-            r#"
-                 SELECT sh.id,
-                        created_at,
-                        updated_at,
-                        m.id           as m_id,
-                        m.title        as m_title,
-                        m.rating   as m_rating,
-                        m.genre        as m_genre,
-                        m.poster_url   as m_poster_url,
-                        shr.id         as shr_id,
-                        shr.time       as shr_time,
-                        shr.price      as shr_price,
-                        r.id           as shr_room_id,
-                        r.name         as shr_room_name,
-                        t.id           as t_id,
-                        t.name         as t_name,
-                        t.location     as t_location
-                 FROM showtime sh
-                          JOIN movie m ON m.id = sh.movie_id
-                          JOIN showtime_room shr ON shr.showtime_id = sh.id
-                          JOIN room r ON r.id = shr.room_id
-                          JOIN theater t ON t.id = r.theater_id
-                 WHERE t.id = {theater_id}
-                 ORDER BY id, created_at DESC;
-                 "#
-        ))
-        .into_json()
+    let slot_filter = Condition::all().add(theater::Column::Id.eq(theater_id));
+
+    let showtimes = showtime::Entity::find()
+        .filter(showtime::Column::Id.in_subquery(showtimes_with_slots(slot_filter.clone())))
+        .order_by_desc(showtime::Column::CreatedAt)
+        .order_by_asc(showtime::Column::Id)
         .all(db)
         .await?;
 
-    map_showtime(showtime_query_results).map_err(|e| e.into())
+    load_showtimes(db, showtimes, slot_filter).await
 }
@@ -0,0 +1,428 @@
+use anyhow::anyhow;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Duration, Utc};
+use entity::{
+    booking, movie, room, sea_orm_active_enums::BookingStatus, showtime, showtime_room, taken_seat,
+    theater, ticket,
+};
+use hmac::{Hmac, Mac};
+use image::{ImageFormat, Luma};
+use qrcode::{QrCode, render::svg};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, DatabaseTransaction,
+    EntityTrait, FromQueryResult, IntoActiveModel, JoinType, QueryFilter, QueryOrder, QuerySelect,
+    RelationTrait, TransactionTrait, prelude::DateTimeWithTimeZone, sea_query::Expr,
+};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::{io::Cursor, str::FromStr};
+use uuid::Uuid;
+
+use crate::{
+    app_error::{AppError, FieldError},
+    app_state::Result,
+    extractors::authenticated_user::AuthenticatedUser,
+    models::{
+        requests::{
+            check_in_request_model::CheckInRequest,
+            get_ticket_qr_request_model::GetTicketQrQueryParams,
+        },
+        ticket_model::{CheckIn, Ticket},
+    },
+};
+
+use super::timezone_service::to_local_time;
+
+/// Smallest width and height of a rendered QR code, in pixels.
+const QR_CODE_SIZE: u32 = 320;
+
+/// What a ticket token vouches for. The token is the claims followed by their HMAC-SHA256, so
+/// changing any of them invalidates the signature.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TicketClaims {
+    pub ticket_id: Uuid,
+    pub showtime_id: Uuid,
+    pub showtime_room_id: i32,
+    pub seat: String,
+}
+
+fn ticket_mac(secret: &str, payload: &str) -> Result<Hmac<Sha256>> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).map_err(|err| anyhow!(err))?;
+    mac.update(payload.as_bytes());
+
+    Ok(mac)
+}
+
+/// Encodes the claims as `<payload>.<signature>`, both base64url encoded.
+pub fn sign_ticket(secret: &str, claims: &TicketClaims) -> Result<String> {
+    let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(claims).map_err(|err| anyhow!(err))?);
+    let signature = URL_SAFE_NO_PAD.encode(ticket_mac(secret, &payload)?.finalize().into_bytes());
+
+    Ok(format!("{payload}.{signature}"))
+}
+
+/// Checks the signature of a ticket token and returns its claims.
+pub fn verify_ticket_token(secret: &str, token: &str) -> Result<TicketClaims> {
+    let invalid_token = || {
+        AppError::Validation(vec![FieldError {
+            field: "token".to_string(),
+            message: "Ticket is not valid".to_string(),
+        }])
+    };
+
+    let (payload, signature) = token.trim().split_once('.').ok_or_else(invalid_token)?;
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature)
+        .map_err(|_| invalid_token())?;
+
+    ticket_mac(secret, payload)?
+        .verify_slice(&signature)
+        .map_err(|_| invalid_token())?;
+
+    URL_SAFE_NO_PAD
+        .decode(payload)
+        .ok()
+        .and_then(|payload| serde_json::from_slice(&payload).ok())
+        .ok_or_else(invalid_token)
+}
+
+fn map_ticket(secret: &str, ticket: ticket::Model) -> Result<Ticket> {
+    let token = sign_ticket(
+        secret,
+        &TicketClaims {
+            ticket_id: ticket.id,
+            showtime_id: ticket.showtime_id,
+            showtime_room_id: ticket.showtime_room_id,
+            seat: ticket.seat_identifier.to_owned(),
+        },
+    )?;
+
+    Ok(Ticket {
+        id: ticket.id.to_string(),
+        booking_id: ticket.booking_id.to_string(),
+        showtime_id: ticket.showtime_id.to_string(),
+        showtime_room_id: ticket.showtime_room_id,
+        seat: ticket.seat_identifier,
+        token,
+        checked_in_at: ticket
+            .checked_in_at
+            .map(|checked_in_at| checked_in_at.with_timezone(&Utc)),
+        voided_at: ticket
+            .voided_at
+            .map(|voided_at| voided_at.with_timezone(&Utc)),
+    })
+}
+
+/// Issues a ticket for each of the seats sold to the booking, as it is confirmed.
+pub async fn issue_tickets(txn: &DatabaseTransaction, booking_id: Uuid) -> Result<()> {
+    let taken_seats = taken_seat::Entity::find()
+        .filter(taken_seat::Column::BookingId.eq(booking_id))
+        .all(txn)
+        .await?;
+
+    if taken_seats.is_empty() {
+        return Ok(());
+    }
+
+    ticket::Entity::insert_many(taken_seats.iter().map(|taken_seat| ticket::ActiveModel {
+        id: Set(Uuid::now_v7()),
+        taken_seat_id: Set(Some(taken_seat.id)),
+        booking_id: Set(booking_id),
+        showtime_id: Set(taken_seat.showtime_id),
+        showtime_room_id: Set(taken_seat.showtime_room_id),
+        seat_identifier: Set(taken_seat.seat_identifier.to_owned()),
+        ..Default::default()
+    }))
+    .exec(txn)
+    .await?;
+
+    Ok(())
+}
+
+/// Voids the tickets of a booking whose seats are being released. The tickets are kept, checked
+/// in or not, but are no longer let in at the door.
+pub async fn void_tickets(txn: &DatabaseTransaction, booking_id: Uuid) -> Result<()> {
+    ticket::Entity::update_many()
+        .col_expr(
+            ticket::Column::VoidedAt,
+            Expr::value(Utc::now().fixed_offset()),
+        )
+        .filter(ticket::Column::BookingId.eq(booking_id))
+        .filter(ticket::Column::VoidedAt.is_null())
+        .exec(txn)
+        .await?;
+
+    Ok(())
+}
+
+/// Tickets are only valid while their booking is confirmed.
+fn require_confirmed(booking: &booking::Model) -> Result<()> {
+    if booking.status != BookingStatus::Confirmed {
+        return Err(AppError::Conflict(format!(
+            "Booking with id: {} is not confirmed, its tickets are not valid",
+            booking.id
+        )));
+    }
+
+    Ok(())
+}
+
+pub async fn get_booking_tickets(
+    db: &DatabaseConnection,
+    secret: &str,
+    user_id: Uuid,
+    booking_id: String,
+) -> Result<Vec<Ticket>> {
+    let booking_id = Uuid::from_str(&booking_id)?;
+
+    let booking = booking::Entity::find_by_id(booking_id)
+        .filter(booking::Column::UserId.eq(user_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("Booking with id: {} does not exist", booking_id))
+        })?;
+    require_confirmed(&booking)?;
+
+    ticket::Entity::find()
+        .filter(ticket::Column::BookingId.eq(booking_id))
+        .order_by_asc(ticket::Column::SeatIdentifier)
+        .order_by_asc(ticket::Column::CreatedAt)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|ticket| map_ticket(secret, ticket))
+        .collect()
+}
+
+/// Renders the ticket's token as a QR code, returning the content type and the image.
+pub async fn render_ticket_qr(
+    db: &DatabaseConnection,
+    secret: &str,
+    user_id: Uuid,
+    ticket_id: String,
+    options: GetTicketQrQueryParams,
+) -> Result<(&'static str, Vec<u8>)> {
+    let ticket_id = Uuid::from_str(&ticket_id)?;
+
+    let format = options.format.as_deref().map(str::trim).unwrap_or("png");
+    if !["png", "svg"].contains(&format) {
+        return Err(AppError::Validation(vec![FieldError {
+            field: "format".to_string(),
+            message: format!("{format} is not one of png or svg"),
+        }]));
+    }
+
+    let not_found = || AppError::NotFound(format!("Ticket with id: {} does not exist", ticket_id));
+
+    let ticket = ticket::Entity::find_by_id(ticket_id)
+        .one(db)
+        .await?
+        .ok_or_else(not_found)?;
+
+    let booking = booking::Entity::find_by_id(ticket.booking_id)
+        .filter(booking::Column::UserId.eq(user_id))
+        .one(db)
+        .await?
+        .ok_or_else(not_found)?;
+    require_confirmed(&booking)?;
+
+    let ticket = map_ticket(secret, ticket)?;
+    let code = QrCode::new(ticket.token.as_bytes()).map_err(|err| anyhow!(err))?;
+
+    if format == "svg" {
+        let image = code
+            .render::<svg::Color>()
+            .min_dimensions(QR_CODE_SIZE, QR_CODE_SIZE)
+            .build();
+
+        return Ok(("image/svg+xml", image.into_bytes()));
+    }
+
+    let image = code
+        .render::<Luma<u8>>()
+        .min_dimensions(QR_CODE_SIZE, QR_CODE_SIZE)
+        .build();
+    let mut png = Cursor::new(vec![]);
+    image
+        .write_to(&mut png, ImageFormat::Png)
+        .map_err(|err| anyhow!(err))?;
+
+    Ok(("image/png", png.into_inner()))
+}
+
+// The slot a ticket is for, together with where and what is showing
+#[derive(FromQueryResult)]
+struct TicketSlotRow {
+    time: DateTimeWithTimeZone,
+    room_name: String,
+    theater_id: Uuid,
+    timezone: String,
+    movie_title: String,
+}
+
+/// Lets a ticket in at the door. A ticket is only accepted once, by staff of the theater it is
+/// for, from `opens_minutes` before its slot starts until `closes_minutes` after.
+pub async fn check_in_ticket(
+    db: &DatabaseConnection,
+    secret: &str,
+    (opens_minutes, closes_minutes): (i64, i64),
+    staff: &AuthenticatedUser,
+    request: CheckInRequest,
+) -> Result<CheckIn> {
+    let claims = verify_ticket_token(secret, &request.token)?;
+    let not_found = || {
+        AppError::NotFound(format!(
+            "Ticket with id: {} does not exist",
+            claims.ticket_id
+        ))
+    };
+
+    let txn = db.begin().await?;
+
+    // Locked so that scanning the same ticket twice at once lets it in only once
+    let ticket = ticket::Entity::find_by_id(claims.ticket_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .filter(|ticket| {
+            ticket.showtime_id == claims.showtime_id
+                && ticket.showtime_room_id == claims.showtime_room_id
+                && ticket.seat_identifier == claims.seat
+        })
+        .ok_or_else(not_found)?;
+
+    let slot = showtime_room::Entity::find_by_id(ticket.showtime_room_id)
+        .select_only()
+        .column(showtime_room::Column::Time)
+        .column_as(room::Column::Name, "room_name")
+        .column_as(theater::Column::Id, "theater_id")
+        .column(theater::Column::Timezone)
+        .column_as(movie::Column::Title, "movie_title")
+        .join(JoinType::InnerJoin, showtime_room::Relation::Room.def())
+        .join(JoinType::InnerJoin, room::Relation::Theater.def())
+        .join(JoinType::InnerJoin, showtime_room::Relation::Showtime.def())
+        .join(JoinType::InnerJoin, showtime::Relation::Movie.def())
+        .into_model::<TicketSlotRow>()
+        .one(&txn)
+        .await?
+        .ok_or_else(not_found)?;
+
+    staff.require_theater_staff(slot.theater_id)?;
+
+    let local_time = |time: DateTime<Utc>| to_local_time(time, &slot.timezone);
+
+    if ticket.voided_at.is_some() {
+        return Err(AppError::Conflict(
+            "Ticket was voided, its booking was cancelled or refunded".to_string(),
+        ));
+    }
+
+    let booking = booking::Entity::find_by_id(ticket.booking_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(not_found)?;
+    require_confirmed(&booking)?;
+    if let Some(checked_in_at) = ticket.checked_in_at {
+        return Err(AppError::Conflict(format!(
+            "Ticket was already scanned at {}",
+            local_time(checked_in_at.with_timezone(&Utc))?.format("%H:%M")
+        )));
+    }
+
+    let now = Utc::now();
+    let time = slot.time.with_timezone(&Utc);
+    let opens_at = time - Duration::minutes(opens_minutes);
+    let closes_at = time + Duration::minutes(closes_minutes);
+
+    if now < opens_at {
+        return Err(AppError::Conflict(format!(
+            "Check-in for this showtime opens at {}",
+            local_time(opens_at)?.format("%Y-%m-%d %H:%M")
+        )));
+    }
+    if now > closes_at {
+        return Err(AppError::Conflict(format!(
+            "Check-in for this showtime closed at {}",
+            local_time(closes_at)?.format("%Y-%m-%d %H:%M")
+        )));
+    }
+
+    let mut checked_in_ticket = ticket.into_active_model();
+    checked_in_ticket.checked_in_at = Set(Some(now.fixed_offset()));
+    checked_in_ticket.checked_in_by = Set(Some(staff.id));
+    let ticket = checked_in_ticket.update(&txn).await?;
+
+    txn.commit().await?;
+
+    Ok(CheckIn {
+        ticket_id: ticket.id.to_string(),
+        booking_id: ticket.booking_id.to_string(),
+        showtime_id: ticket.showtime_id.to_string(),
+        showtime_room_id: ticket.showtime_room_id,
+        seat: ticket.seat_identifier,
+        movie_title: slot.movie_title,
+        room_name: slot.room_name,
+        time,
+        local_time: local_time(time)?,
+        checked_in_at: local_time(now)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+    use uuid::Uuid;
+
+    use crate::app_error::AppError;
+
+    use super::{TicketClaims, sign_ticket, verify_ticket_token};
+
+    const SECRET: &str = "ticket-signing-secret-for-the-tests";
+
+    fn claims() -> TicketClaims {
+        TicketClaims {
+            ticket_id: Uuid::now_v7(),
+            showtime_id: Uuid::now_v7(),
+            showtime_room_id: 1,
+            seat: "A1".to_string(),
+        }
+    }
+
+    #[test]
+    fn signed_ticket_verifies() {
+        let claims = claims();
+        let token = sign_ticket(SECRET, &claims).unwrap();
+
+        let verified = verify_ticket_token(SECRET, &token).unwrap();
+
+        assert_eq!(verified.ticket_id, claims.ticket_id);
+        assert_eq!(verified.showtime_id, claims.showtime_id);
+        assert_eq!(verified.showtime_room_id, claims.showtime_room_id);
+        assert_eq!(verified.seat, claims.seat);
+    }
+
+    #[test]
+    fn tampered_ticket_is_rejected() {
+        let mut claims = claims();
+        let token = sign_ticket(SECRET, &claims).unwrap();
+        let (_, signature) = token.split_once('.').unwrap();
+
+        claims.seat = "B2".to_string();
+        let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims).unwrap());
+
+        let result = verify_ticket_token(SECRET, &format!("{payload}.{signature}"));
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn ticket_signed_with_another_key_is_rejected() {
+        let token = sign_ticket("another-ticket-signing-secret-entirely", &claims()).unwrap();
+
+        let result = verify_ticket_token(SECRET, &token);
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+}
@@ -0,0 +1,41 @@
+use anyhow::anyhow;
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDateTime, Timelike, Utc};
+use jiff::{Timestamp, civil, tz::TimeZone};
+
+use crate::app_state::Result;
+
+/// Looks up an IANA timezone such as `Asia/Jakarta`.
+pub fn find_timezone(name: &str) -> Option<TimeZone> {
+    TimeZone::get(name).ok()
+}
+
+/// The wall-clock time of `time` in the timezone, carrying the UTC offset in effect at that
+/// instant.
+pub fn to_local_time(time: DateTime<Utc>, timezone: &str) -> Result<DateTime<FixedOffset>> {
+    let tz = find_timezone(timezone).ok_or_else(|| anyhow!("Unknown timezone: {timezone}"))?;
+    let timestamp = Timestamp::from_second(time.timestamp()).map_err(|e| anyhow!(e))?;
+
+    let offset = FixedOffset::east_opt(tz.to_offset(timestamp).seconds())
+        .ok_or_else(|| anyhow!("Offset of timezone {timezone} is out of range"))?;
+
+    Ok(time.with_timezone(&offset))
+}
+
+/// The instant a wall-clock time in the timezone refers to, or `None` when a clock change skips
+/// or repeats that time.
+pub fn from_local_time(time: NaiveDateTime, tz: &TimeZone) -> Option<DateTime<Utc>> {
+    let time = civil::DateTime::new(
+        time.year().try_into().ok()?,
+        time.month() as i8,
+        time.day() as i8,
+        time.hour() as i8,
+        time.minute() as i8,
+        time.second() as i8,
+        0,
+    )
+    .ok()?;
+
+    let zoned = tz.to_ambiguous_zoned(time).unambiguous().ok()?;
+
+    DateTime::from_timestamp(zoned.timestamp().as_second(), 0)
+}